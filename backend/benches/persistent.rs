@@ -0,0 +1,129 @@
+use std::{collections::HashMap, rc::Rc};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use lispers_backend::{PersistentVector, PersistentMap};
+
+const SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+// Every benchmark keeps all intermediate versions alive, which is what a
+// functional program does when it holds on to older values.
+
+fn vector_push(c: &mut Criterion) {
+  let mut group = c.benchmark_group("vector-push");
+
+  for size in SIZES {
+    group.bench_with_input(BenchmarkId::new("persistent", size), &size, |b, &size| {
+      b.iter(|| {
+        let mut versions = Vec::with_capacity(size);
+        let mut vector = PersistentVector::new();
+
+        for i in 0..size {
+          vector = vector.push(i);
+          versions.push(vector.clone());
+        }
+
+        versions
+      })
+    });
+
+    group.bench_with_input(BenchmarkId::new("copy-on-write", size), &size, |b, &size| {
+      b.iter(|| {
+        let mut versions = Vec::with_capacity(size);
+        let mut vector = Rc::new(Vec::new());
+
+        for i in 0..size {
+          let mut next = vector.as_ref().clone();
+          next.push(i);
+          vector = Rc::new(next);
+          versions.push(vector.clone());
+        }
+
+        versions
+      })
+    });
+  }
+
+  group.finish();
+}
+
+fn vector_set(c: &mut Criterion) {
+  let mut group = c.benchmark_group("vector-set");
+
+  for size in SIZES {
+    let persistent: PersistentVector<usize> = (0..size).collect();
+    let cow: Rc<Vec<usize>> = Rc::new((0..size).collect());
+
+    group.bench_with_input(BenchmarkId::new("persistent", size), &size, |b, &size| {
+      b.iter(|| {
+        let mut versions = Vec::with_capacity(size);
+        let mut vector = persistent.clone();
+
+        for i in 0..size {
+          vector = vector.set(i, i * 2).unwrap_or(vector);
+          versions.push(vector.clone());
+        }
+
+        versions
+      })
+    });
+
+    group.bench_with_input(BenchmarkId::new("copy-on-write", size), &size, |b, &size| {
+      b.iter(|| {
+        let mut versions = Vec::with_capacity(size);
+        let mut vector = cow.clone();
+
+        for i in 0..size {
+          let mut next = vector.as_ref().clone();
+          next[i] = i * 2;
+          vector = Rc::new(next);
+          versions.push(vector.clone());
+        }
+
+        versions
+      })
+    });
+  }
+
+  group.finish();
+}
+
+fn map_insert(c: &mut Criterion) {
+  let mut group = c.benchmark_group("map-insert");
+
+  for size in SIZES {
+    group.bench_with_input(BenchmarkId::new("persistent", size), &size, |b, &size| {
+      b.iter(|| {
+        let mut versions = Vec::with_capacity(size);
+        let mut map = PersistentMap::new();
+
+        for i in 0..size {
+          map = map.insert(i, i);
+          versions.push(map.clone());
+        }
+
+        versions
+      })
+    });
+
+    group.bench_with_input(BenchmarkId::new("copy-on-write", size), &size, |b, &size| {
+      b.iter(|| {
+        let mut versions = Vec::with_capacity(size);
+        let mut map = Rc::new(HashMap::new());
+
+        for i in 0..size {
+          let mut next = map.as_ref().clone();
+          next.insert(i, i);
+          map = Rc::new(next);
+          versions.push(map.clone());
+        }
+
+        versions
+      })
+    });
+  }
+
+  group.finish();
+}
+
+criterion_group!(benches, vector_push, vector_set, map_insert);
+criterion_main!(benches);
@@ -0,0 +1,269 @@
+//! Lisp files as configuration.
+//!
+//! The file is evaluated in a sandbox (see `Interpreter::sandbox`), then
+//! deserialized into a Rust type: when its last top-level expression is a
+//! `def`, the deserialized value is a map from every top-level definition
+//! to its value, otherwise it is the value of the last expression.
+//!
+//! ```lisp
+//! (def name "server")
+//! (def port (+ 8000 80))
+//! ```
+//!
+//! Errors are located at the expression that failed to evaluate, or at the
+//! innermost literal expression containing the value that failed to
+//! deserialize.
+
+use std::{
+  path::{Path, PathBuf},
+  collections::HashMap,
+  rc::Rc,
+  cell::RefCell,
+};
+
+use serde::de::DeserializeOwned;
+use lispers_common::{backend::DefaultBackend, symbol::SymbolUsize};
+use lispers_frontend::{SExpression, Literal};
+
+use crate::prelude::RuntimeError;
+use crate::data::Value;
+use crate::env::Env;
+use crate::interpreter::Interpreter;
+use crate::serialization::{self, PathSegment};
+
+type Symbol = SymbolUsize;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+  pub filename: Option<PathBuf>,
+  pub line: usize,
+  pub column: usize,
+}
+
+impl std::fmt::Display for Location {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    let filename = self.filename
+      .as_ref()
+      .map(|path| path.display().to_string())
+      .unwrap_or("<>".to_string());
+
+    write!(f, "{}[{};{}]", filename, self.line, self.column)
+  }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+  IOError(std::io::Error),
+  /// Syntax errors carry their own location.
+  Eval { error: RuntimeError, location: Option<Location> },
+  Deserialize { error: serialization::Error, location: Option<Location> },
+}
+
+impl ConfigError {
+  pub fn location(&self) -> Option<&Location> {
+    match self {
+      Self::IOError(..) => None,
+      Self::Eval { location, .. } | Self::Deserialize { location, .. } => location.as_ref(),
+    }
+  }
+}
+
+impl std::fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    if let Some(location) = self.location() {
+      write!(f, "{} ", location)?;
+    }
+
+    match self {
+      Self::IOError(err) => write!(f, "IOError: {}", err),
+      Self::Eval { error, .. } => write!(f, "{}", error),
+      Self::Deserialize { error, .. } => write!(f, "{}", error),
+    }
+  }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<std::io::Error> for ConfigError {
+  fn from(err: std::io::Error) -> Self {
+    Self::IOError(err)
+  }
+}
+
+pub fn from_file<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T, ConfigError> {
+  let input = std::fs::read_to_string(path.as_ref())?;
+  from_source(Some(path.as_ref().to_path_buf()), &input)
+}
+
+pub fn from_str<T: DeserializeOwned>(input: &str) -> Result<T, ConfigError> {
+  from_source(None, input)
+}
+
+fn from_source<T: DeserializeOwned>(filename: Option<PathBuf>, input: &str) -> Result<T, ConfigError> {
+  let mut config = Config {
+    interpreter: Interpreter::new(),
+    filename,
+    input,
+  };
+
+  let sexpressions = config.interpreter
+    .parse_source(config.filename.clone(), input)
+    .map_err(|error| ConfigError::Eval { error, location: None })?;
+
+  // The interpreter is fresh and dropped once the config is read, so closing
+  // its default ports does not affect any other evaluation.
+  let base = config.interpreter.sandbox();
+  let env = Rc::new(RefCell::new(Env::extend(base)));
+  let mut value = Value::default();
+
+  for (index, sexpression) in sexpressions.iter().enumerate() {
+    value = config.interpreter
+      .eval_sexpression(env.clone(), sexpression)
+      .map_err(|error| ConfigError::Eval { error, location: config.locate(&[index]) })?;
+  }
+
+  let definitions = matches!(sexpressions.last(), Some(sexpression) if config.is_def(sexpression));
+
+  if definitions {
+    let env = env.borrow();
+    let entries: HashMap<Value<Symbol>, Value<Symbol>> = env
+      .symbols()
+      .into_iter()
+      .filter_map(|sym| Some((Value::Keyword(sym.into()), env.get(sym)?)))
+      .collect();
+
+    value = Value::Map(Rc::new(entries));
+  }
+
+  config.interpreter.from_value(&value).map_err(|error| {
+    let path = config.source_path(&sexpressions, definitions, error.path());
+    let location = path.and_then(|path| config.locate(&path));
+    ConfigError::Deserialize { error, location }
+  })
+}
+
+struct Config<'a> {
+  interpreter: Interpreter<Symbol, DefaultBackend<Symbol>>,
+  filename: Option<PathBuf>,
+  input: &'a str,
+}
+
+impl Config<'_> {
+  fn locate(&self, path: &[usize]) -> Option<Location> {
+    let (line, column) = lispers_frontend::locate(self.input, path)?;
+    Some(Location { filename: self.filename.clone(), line, column })
+  }
+
+  fn symbol_name<'s>(&'s self, sexpression: &'s SExpression<Symbol>) -> Option<&'s str> {
+    match sexpression {
+      SExpression::Literal(Literal::Symbol(sym)) => self.interpreter.symbol_name(*sym),
+      _ => None,
+    }
+  }
+
+  fn key_name(&self, sexpression: &SExpression<Symbol>) -> Option<String> {
+    match sexpression {
+      SExpression::Literal(Literal::Symbol(sym) | Literal::Keyword(sym)) => {
+        self.interpreter.symbol_name(*sym).map(str::to_string)
+      },
+      SExpression::Literal(Literal::String(s)) => Some(s.clone()),
+      SExpression::Literal(Literal::Integer(n)) => Some(n.to_string()),
+      _ => None,
+    }
+  }
+
+  fn is_def(&self, sexpression: &SExpression<Symbol>) -> bool {
+    match sexpression {
+      SExpression::List(items) => items.first().and_then(|head| self.symbol_name(head)) == Some("def"),
+      _ => false,
+    }
+  }
+
+  /// Child indices leading from the top-level expressions to the innermost
+  /// expression producing the value at `path`.
+  fn source_path(
+    &self,
+    sexpressions: &[SExpression<Symbol>],
+    definitions: bool,
+    path: &[PathSegment],
+  ) -> Option<Vec<usize>> {
+    let (mut indices, mut node, path) = match (definitions, path) {
+      (true, [PathSegment::Key(name), rest @ ..]) => {
+        let index = sexpressions.iter().rposition(|sexpression| {
+          matches!(sexpression, SExpression::List(items)
+            if self.is_def(sexpression)
+            && items.get(1).and_then(|target| self.symbol_name(target)) == Some(name))
+        })?;
+
+        match &sexpressions[index] {
+          SExpression::List(items) if items.len() > 2 => (vec![index, 2], &items[2], rest),
+          _ => (vec![index], &sexpressions[index], rest),
+        }
+      },
+      (true, _) => return None,
+      (false, _) => {
+        let index = sexpressions.len().checked_sub(1)?;
+        (vec![index], &sexpressions[index], path)
+      },
+    };
+
+    let mut quoted = false;
+
+    for segment in path {
+      if let SExpression::List(items) = node {
+        if !quoted && items.len() == 2 && self.symbol_name(&items[0]) == Some("quote") {
+          indices.push(1);
+          node = &items[1];
+          quoted = true;
+        }
+      }
+
+      match self.child(node, segment, quoted) {
+        Some((index, child)) => {
+          indices.push(index);
+          node = child;
+        },
+        None => break,
+      }
+    }
+
+    Some(indices)
+  }
+
+  fn child<'s>(
+    &self,
+    node: &'s SExpression<Symbol>,
+    segment: &PathSegment,
+    quoted: bool,
+  ) -> Option<(usize, &'s SExpression<Symbol>)> {
+    match (node, segment) {
+      (SExpression::Vector(items), PathSegment::Index(i)) => Some((*i, items.get(*i)?)),
+      (SExpression::List(items), PathSegment::Index(i)) if quoted => Some((*i, items.get(*i)?)),
+      (SExpression::Map(entries), PathSegment::Key(key)) => {
+        let j = entries
+          .iter()
+          .rposition(|(k, _)| self.key_name(k).as_ref() == Some(key))?;
+
+        Some((2 * j + 1, &entries[j].1))
+      },
+      (SExpression::List(items), segment) if !quoted => {
+        let head = items.first().and_then(|head| self.symbol_name(head))?;
+
+        match (head, segment) {
+          ("list" | "vector" | "persistent-vector", PathSegment::Index(i)) => {
+            Some((i + 1, items.get(i + 1)?))
+          },
+          ("hash-map" | "persistent-hash-map", PathSegment::Key(key)) => {
+            let j = items[1..]
+              .chunks(2)
+              .rposition(|pair| self.key_name(&pair[0]).as_ref() == Some(key))?;
+
+            Some((2 * j + 2, items.get(2 * j + 2)?))
+          },
+          _ => None,
+        }
+      },
+      _ => None,
+    }
+  }
+}
@@ -7,3 +7,18 @@ pub struct ConsCell<S: Symbol> {
   pub car: Value<S>,
   pub cdr: Option<Rc<RefCell<ConsCell<S>>>>,
 }
+
+/// Unlink the tail one cell at a time, so dropping a long list does not
+/// recurse once per cell.
+impl<S: Symbol> Drop for ConsCell<S> {
+  fn drop(&mut self) {
+    let mut next = self.cdr.take();
+
+    while let Some(cell) = next {
+      next = match Rc::try_unwrap(cell) {
+        Ok(cell) => cell.borrow_mut().cdr.take(),
+        Err(_) => None,
+      };
+    }
+  }
+}
@@ -3,12 +3,63 @@ use lispers_common::Symbol;
 
 use crate::prelude::*;
 use crate::env::Env;
-use super::{Value, Lambda};
+use super::{Value, Lambda, Port};
 
-pub type NativeFn<S> = fn(Rc<RefCell<Env<S>>>, Vec<Value<S>>) -> Result<Value<S>>;
+/// Gives native functions access to the interpreter that called them.
+pub trait Context<S: Symbol> {
+  /// Call a function value (or a keyword, as a map lookup) with already
+  /// evaluated arguments.
+  fn apply(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    func: Value<S>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>>;
+
+  fn intern(&mut self, name: &str) -> S;
+
+  /// Parse source text into unevaluated data.
+  fn read(&mut self, input: &str) -> Result<Vec<Value<S>>>;
+
+  /// Human-readable representation of a value.
+  fn display(&self, value: &Value<S>) -> String;
+
+  /// Machine-readable representation of a value, accepted by `read`.
+  fn write(&self, value: &Value<S>) -> String;
+
+  /// Port that `read-line`, `read-char` and `read` read from by default.
+  fn input_port(&self) -> Port;
+
+  /// Port that `println` and `write-string` write to by default.
+  fn output_port(&self) -> Port;
+
+  /// Redirect the default output, returning the previous port.
+  fn replace_output_port(&mut self, port: Port) -> Port;
+}
+
+pub type NativeFn<S> = fn(
+  &mut dyn Context<S>,
+  Rc<RefCell<Env<S>>>,
+  Vec<Value<S>>,
+) -> Result<Value<S>>;
 
 #[derive(Clone)]
 pub enum Function<S: Symbol> {
-  NativeFn(NativeFn<S>),
-  Lambda(Lambda<S>),
+  NativeFn { name: S, func: NativeFn<S> },
+  Lambda(Rc<Lambda<S>>),
+}
+
+/// Functions are equal only to themselves: natives by name and code pointer,
+/// lambdas by the allocation created when the `lambda` form was evaluated.
+impl<S: Symbol> PartialEq for Function<S> {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (
+        Function::NativeFn { name: name_a, func: func_a },
+        Function::NativeFn { name: name_b, func: func_b },
+      ) => name_a == name_b && std::ptr::fn_addr_eq(*func_a, *func_b),
+      (Function::Lambda(a), Function::Lambda(b)) => Rc::ptr_eq(a, b),
+      _ => false,
+    }
+  }
 }
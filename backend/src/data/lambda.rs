@@ -1,9 +1,51 @@
+use std::{rc::Rc, cell::RefCell};
 use lispers_common::Symbol;
 
+use crate::env::Env;
 use super::Value;
 
+/// Parameter list of a lambda:
+/// `(required ... &optional (name default) ... &rest name &key (name default) ...)`.
+///
+/// `(required ... . name)` is a shorthand for `&rest`. Required parameters
+/// may be destructuring patterns. Defaults are evaluated at call time, in a
+/// scope where the preceding parameters are bound.
+#[derive(Clone)]
+pub struct Params<S: Symbol> {
+  pub required: Vec<Value<S>>,
+  pub optional: Vec<(S, Value<S>)>,
+  pub rest: Option<S>,
+  pub keys: Vec<(S, Value<S>)>,
+}
+
+impl<S: Symbol> Params<S> {
+  pub fn positional(required: Vec<Value<S>>) -> Self {
+    Self {
+      required,
+      optional: Vec::new(),
+      rest: None,
+      keys: Vec::new(),
+    }
+  }
+
+  /// Minimum and maximum number of arguments, `None` meaning unbounded.
+  pub fn arity(&self) -> (usize, Option<usize>) {
+    let min = self.required.len();
+
+    let max = match self.rest {
+      Some(_) => None,
+      None => Some(min + self.optional.len() + 2 * self.keys.len()),
+    };
+
+    (min, max)
+  }
+}
+
+/// A user-defined function, closing over the environment it was created in.
 #[derive(Clone)]
 pub struct Lambda<S: Symbol> {
-  pub params: Vec<S>,
-  pub body: Box<Value<S>>,
+  pub name: Option<S>,
+  pub params: Params<S>,
+  pub body: Vec<Value<S>>,
+  pub env: Rc<RefCell<Env<S>>>,
 }
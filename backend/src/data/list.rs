@@ -19,6 +19,15 @@ impl<S: Symbol> List<S> {
     self.head.is_none()
   }
 
+  /// Whether both lists start at the same cons cell.
+  pub fn ptr_eq(&self, other: &Self) -> bool {
+    match (&self.head, &other.head) {
+      (None, None) => true,
+      (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+      _ => false,
+    }
+  }
+
   pub fn car(&self) -> Result<Value<S>> {
     self.head
       .as_ref()
@@ -44,7 +53,20 @@ impl<S: Symbol> List<S> {
   }
 }
 
-impl<'a, S: Symbol> IntoIterator for &'a List<S> {
+impl<S: Symbol> FromIterator<Value<S>> for List<S> {
+  fn from_iter<I: IntoIterator<Item = Value<S>>>(iter: I) -> Self {
+    let items: Vec<Value<S>> = iter.into_iter().collect();
+    let mut list = Self::NIL;
+
+    for item in items.into_iter().rev() {
+      list = list.cons(item);
+    }
+
+    list
+  }
+}
+
+impl<S: Symbol> IntoIterator for &List<S> {
   type Item = Value<S>;
   type IntoIter = ListIterator<S>;
 
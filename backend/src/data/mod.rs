@@ -3,11 +3,17 @@ mod cell;
 mod list;
 mod function;
 mod lambda;
+mod persistent_vector;
+mod persistent_map;
+mod port;
 
 pub use self::{
-  value::{Value, Sym},
+  value::{Value, Type, Sym},
   cell::ConsCell,
   list::List,
-  function::Function,
-  lambda::Lambda,
+  function::{Function, NativeFn, Context},
+  lambda::{Lambda, Params},
+  persistent_vector::PersistentVector,
+  persistent_map::PersistentMap,
+  port::Port,
 };
@@ -0,0 +1,577 @@
+use std::{
+  collections::hash_map::DefaultHasher,
+  hash::{Hash, Hasher},
+  rc::Rc,
+};
+
+const BITS: u32 = 5;
+const MASK: u64 = (1 << BITS) - 1;
+
+enum Entry<K, V> {
+  Leaf(u64, K, V),
+  Node(Rc<Node<K, V>>),
+}
+
+enum Node<K, V> {
+  Bitmap { bitmap: u32, entries: Vec<Entry<K, V>> },
+  Collision { hash: u64, pairs: Vec<(K, V)> },
+}
+
+enum Removal<K, V> {
+  NotFound,
+  Removed(Option<Rc<Node<K, V>>>),
+}
+
+/// Immutable hash map implemented as a Hash Array Mapped Trie.
+///
+/// Each level of the trie consumes 5 bits of the key's hash, updates copy
+/// only the nodes along the path to the modified entry.
+pub struct PersistentMap<K, V> {
+  len: usize,
+  root: Option<Rc<Node<K, V>>>,
+}
+
+pub struct PersistentMapIterator<'a, K, V> {
+  stack: Vec<&'a Node<K, V>>,
+  entries: std::slice::Iter<'a, Entry<K, V>>,
+  pairs: std::slice::Iter<'a, (K, V)>,
+}
+
+impl<K: Clone, V: Clone> Clone for Entry<K, V> {
+  fn clone(&self) -> Self {
+    match self {
+      Self::Leaf(hash, key, val) => Self::Leaf(*hash, key.clone(), val.clone()),
+      Self::Node(node) => Self::Node(node.clone()),
+    }
+  }
+}
+
+impl<K, V> Clone for PersistentMap<K, V> {
+  fn clone(&self) -> Self {
+    Self {
+      len: self.len,
+      root: self.root.clone(),
+    }
+  }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for PersistentMap<K, V> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+fn hash_of<K: Hash>(key: &K) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  key.hash(&mut hasher);
+  hasher.finish()
+}
+
+fn fragment(hash: u64, shift: u32) -> u32 {
+  if shift >= u64::BITS {
+    0
+  }
+  else {
+    ((hash >> shift) & MASK) as u32
+  }
+}
+
+fn index_of(bitmap: u32, bit: u32) -> usize {
+  (bitmap & (bit - 1)).count_ones() as usize
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Node<K, V> {
+  fn get(&self, shift: u32, hash: u64, key: &K) -> Option<&V> {
+    match self {
+      Self::Bitmap { bitmap, entries } => {
+        let bit = 1 << fragment(hash, shift);
+
+        if bitmap & bit == 0 {
+          return None;
+        }
+
+        match &entries[index_of(*bitmap, bit)] {
+          Entry::Leaf(_, k, v) if k == key => Some(v),
+          Entry::Leaf(..) => None,
+          Entry::Node(node) => node.get(shift + BITS, hash, key),
+        }
+      },
+      Self::Collision { pairs, .. } => {
+        pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+      },
+    }
+  }
+
+  fn insert(&self, shift: u32, hash: u64, key: K, val: V) -> (Rc<Self>, bool) {
+    match self {
+      Self::Bitmap { bitmap, entries } => {
+        let bit = 1 << fragment(hash, shift);
+        let idx = index_of(*bitmap, bit);
+        let mut entries = entries.clone();
+
+        if bitmap & bit == 0 {
+          entries.insert(idx, Entry::Leaf(hash, key, val));
+          let node = Self::Bitmap { bitmap: bitmap | bit, entries };
+          return (Rc::new(node), true);
+        }
+
+        let added = match &entries[idx] {
+          Entry::Leaf(_, k, _) if *k == key => {
+            entries[idx] = Entry::Leaf(hash, key, val);
+            false
+          },
+          Entry::Leaf(other_hash, other_key, other_val) => {
+            let node = Self::merge(
+              shift + BITS,
+              (*other_hash, other_key.clone(), other_val.clone()),
+              (hash, key, val),
+            );
+            entries[idx] = Entry::Node(node);
+            true
+          },
+          Entry::Node(node) => {
+            let (node, added) = node.insert(shift + BITS, hash, key, val);
+            entries[idx] = Entry::Node(node);
+            added
+          },
+        };
+
+        (Rc::new(Self::Bitmap { bitmap: *bitmap, entries }), added)
+      },
+      Self::Collision { hash: collision_hash, pairs } if *collision_hash == hash => {
+        let mut pairs = pairs.clone();
+
+        let added = match pairs.iter().position(|(k, _)| *k == key) {
+          Some(idx) => {
+            pairs[idx] = (key, val);
+            false
+          },
+          None => {
+            pairs.push((key, val));
+            true
+          },
+        };
+
+        (Rc::new(Self::Collision { hash, pairs }), added)
+      },
+      Self::Collision { hash: collision_hash, pairs } => {
+        let collision = Self::Collision { hash: *collision_hash, pairs: pairs.clone() };
+        let wrapper = Self::Bitmap {
+          bitmap: 1 << fragment(*collision_hash, shift),
+          entries: vec![Entry::Node(Rc::new(collision))],
+        };
+        wrapper.insert(shift, hash, key, val)
+      },
+    }
+  }
+
+  fn remove(&self, shift: u32, hash: u64, key: &K) -> Removal<K, V> {
+    match self {
+      Self::Bitmap { bitmap, entries } => {
+        let bit = 1 << fragment(hash, shift);
+
+        if bitmap & bit == 0 {
+          return Removal::NotFound;
+        }
+
+        let idx = index_of(*bitmap, bit);
+
+        let replacement = match &entries[idx] {
+          Entry::Leaf(_, k, _) if k == key => None,
+          Entry::Leaf(..) => return Removal::NotFound,
+          Entry::Node(node) => match node.remove(shift + BITS, hash, key) {
+            Removal::NotFound => return Removal::NotFound,
+            Removal::Removed(None) => None,
+            Removal::Removed(Some(node)) => Some(Self::collapse(node)),
+          },
+        };
+
+        let mut entries = entries.clone();
+
+        match replacement {
+          Some(entry) => {
+            entries[idx] = entry;
+            Removal::Removed(Some(Rc::new(Self::Bitmap { bitmap: *bitmap, entries })))
+          },
+          None if entries.len() == 1 => Removal::Removed(None),
+          None => {
+            entries.remove(idx);
+            let bitmap = bitmap & !bit;
+            Removal::Removed(Some(Rc::new(Self::Bitmap { bitmap, entries })))
+          },
+        }
+      },
+      Self::Collision { hash, pairs } => {
+        match pairs.iter().position(|(k, _)| k == key) {
+          None => Removal::NotFound,
+          Some(_) if pairs.len() == 1 => Removal::Removed(None),
+          Some(idx) => {
+            let mut pairs = pairs.clone();
+            pairs.remove(idx);
+            Removal::Removed(Some(Rc::new(Self::Collision { hash: *hash, pairs })))
+          },
+        }
+      },
+    }
+  }
+
+  fn merge(shift: u32, a: (u64, K, V), b: (u64, K, V)) -> Rc<Self> {
+    if a.0 == b.0 {
+      return Rc::new(Self::Collision {
+        hash: a.0,
+        pairs: vec![(a.1, a.2), (b.1, b.2)],
+      });
+    }
+
+    let frag_a = fragment(a.0, shift);
+    let frag_b = fragment(b.0, shift);
+
+    let node = if frag_a == frag_b {
+      Self::Bitmap {
+        bitmap: 1 << frag_a,
+        entries: vec![Entry::Node(Self::merge(shift + BITS, a, b))],
+      }
+    }
+    else {
+      let leaf_a = Entry::Leaf(a.0, a.1, a.2);
+      let leaf_b = Entry::Leaf(b.0, b.1, b.2);
+
+      let entries = if frag_a < frag_b {
+        vec![leaf_a, leaf_b]
+      }
+      else {
+        vec![leaf_b, leaf_a]
+      };
+
+      Self::Bitmap { bitmap: (1 << frag_a) | (1 << frag_b), entries }
+    };
+
+    Rc::new(node)
+  }
+
+  /// Inline sub-tries that were reduced to a single key/value pair.
+  fn collapse(node: Rc<Self>) -> Entry<K, V> {
+    match node.as_ref() {
+      Self::Bitmap { entries, .. } if entries.len() == 1 => {
+        match &entries[0] {
+          leaf @ Entry::Leaf(..) => leaf.clone(),
+          Entry::Node(..) => Entry::Node(node),
+        }
+      },
+      Self::Collision { hash, pairs } if pairs.len() == 1 => {
+        let (key, val) = pairs[0].clone();
+        Entry::Leaf(*hash, key, val)
+      },
+      _ => Entry::Node(node),
+    }
+  }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> PersistentMap<K, V> {
+  pub fn new() -> Self {
+    Self { len: 0, root: None }
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Whether both maps share the same root node.
+  pub fn ptr_eq(&self, other: &Self) -> bool {
+    match (&self.root, &other.root) {
+      (None, None) => true,
+      (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+      _ => false,
+    }
+  }
+
+  pub fn get(&self, key: &K) -> Option<&V> {
+    self.root
+      .as_ref()
+      .and_then(|root| root.get(0, hash_of(key), key))
+  }
+
+  pub fn contains_key(&self, key: &K) -> bool {
+    self.get(key).is_some()
+  }
+
+  pub fn insert(&self, key: K, val: V) -> Self {
+    let hash = hash_of(&key);
+
+    let (root, added) = match &self.root {
+      Some(root) => root.insert(0, hash, key, val),
+      None => {
+        let root = Node::Bitmap {
+          bitmap: 1 << fragment(hash, 0),
+          entries: vec![Entry::Leaf(hash, key, val)],
+        };
+        (Rc::new(root), true)
+      },
+    };
+
+    Self {
+      len: if added { self.len + 1 } else { self.len },
+      root: Some(root),
+    }
+  }
+
+  pub fn remove(&self, key: &K) -> Self {
+    let removal = match &self.root {
+      Some(root) => root.remove(0, hash_of(key), key),
+      None => Removal::NotFound,
+    };
+
+    match removal {
+      Removal::NotFound => self.clone(),
+      Removal::Removed(root) => Self { len: self.len - 1, root },
+    }
+  }
+
+  pub fn iter(&self) -> PersistentMapIterator<'_, K, V> {
+    PersistentMapIterator {
+      stack: self.root.iter().map(|root| root.as_ref()).collect(),
+      entries: [].iter(),
+      pairs: [].iter(),
+    }
+  }
+
+  pub fn keys(&self) -> impl Iterator<Item = &K> {
+    self.iter().map(|(key, _)| key)
+  }
+
+  pub fn values(&self) -> impl Iterator<Item = &V> {
+    self.iter().map(|(_, val)| val)
+  }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> FromIterator<(K, V)> for PersistentMap<K, V> {
+  fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+    iter.into_iter().fold(Self::new(), |map, (key, val)| map.insert(key, val))
+  }
+}
+
+impl<'a, K: Hash + Eq + Clone, V: Clone> IntoIterator for &'a PersistentMap<K, V> {
+  type Item = (&'a K, &'a V);
+  type IntoIter = PersistentMapIterator<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, K, V> Iterator for PersistentMapIterator<'a, K, V> {
+  type Item = (&'a K, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Some((key, val)) = self.pairs.next() {
+        return Some((key, val));
+      }
+
+      match self.entries.next() {
+        Some(Entry::Leaf(_, key, val)) => return Some((key, val)),
+        Some(Entry::Node(node)) => self.stack.push(node.as_ref()),
+        None => match self.stack.pop()? {
+          Node::Bitmap { entries, .. } => self.entries = entries.iter(),
+          Node::Collision { pairs, .. } => self.pairs = pairs.iter(),
+        },
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Key whose hash only depends on `bucket`, to force collisions.
+  #[derive(Clone, Debug, PartialEq, Eq)]
+  struct Colliding {
+    id: u32,
+    bucket: u64,
+  }
+
+  impl Hash for Colliding {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+      self.bucket.hash(state);
+    }
+  }
+
+  fn key(id: u32, bucket: u64) -> Colliding {
+    Colliding { id, bucket }
+  }
+
+  fn collisions<K, V>(node: &Node<K, V>) -> usize {
+    match node {
+      Node::Bitmap { entries, .. } => entries
+        .iter()
+        .map(|entry| match entry {
+          Entry::Leaf(..) => 0,
+          Entry::Node(node) => collisions(node),
+        })
+        .sum(),
+      Node::Collision { .. } => 1,
+    }
+  }
+
+  fn collision_count<K, V>(map: &PersistentMap<K, V>) -> usize {
+    map.root.as_ref().map_or(0, |root| collisions(root))
+  }
+
+  /// Bucket with a different hash but the same first fragment as `bucket`.
+  fn neighbour(bucket: u64) -> u64 {
+    let hash = hash_of(&bucket);
+
+    (bucket + 1..)
+      .find(|other| {
+        let other = hash_of(other);
+        other != hash && fragment(other, 0) == fragment(hash, 0)
+      })
+      .unwrap()
+  }
+
+  #[test]
+  fn insert_and_get() {
+    let map: PersistentMap<u32, u32> = (0..1000).map(|n| (n, n * 2)).collect();
+
+    assert_eq!(map.len(), 1000);
+
+    for n in 0..1000 {
+      assert_eq!(map.get(&n), Some(&(n * 2)));
+    }
+
+    assert_eq!(map.get(&1000), None);
+    assert!(!map.contains_key(&1000));
+  }
+
+  #[test]
+  fn insert_existing_key() {
+    let map = PersistentMap::new().insert("a", 1).insert("a", 2);
+
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get(&"a"), Some(&2));
+  }
+
+  #[test]
+  fn remove() {
+    let map: PersistentMap<u32, u32> = (0..1000).map(|n| (n, n)).collect();
+    let map = (0..1000).step_by(2).fold(map, |map, n| map.remove(&n));
+
+    assert_eq!(map.len(), 500);
+
+    for n in 0..1000 {
+      assert_eq!(map.contains_key(&n), n % 2 == 1);
+    }
+
+    let map = (0..1000).fold(map, |map, n| map.remove(&n));
+    assert!(map.is_empty());
+    assert!(map.root.is_none());
+  }
+
+  #[test]
+  fn remove_missing_key() {
+    let map = PersistentMap::new().insert(1, 1);
+    let removed = map.remove(&2);
+
+    assert_eq!(removed.len(), 1);
+    assert!(map.ptr_eq(&removed));
+    assert!(PersistentMap::<u32, u32>::new().remove(&1).is_empty());
+  }
+
+  #[test]
+  fn old_versions_are_unchanged() {
+    let base: PersistentMap<u32, u32> = (0..100).map(|n| (n, n)).collect();
+    let inserted = base.insert(100, 100).insert(0, 42);
+    let removed = base.remove(&50);
+
+    assert_eq!(base.len(), 100);
+    assert_eq!(base.get(&0), Some(&0));
+    assert_eq!(base.get(&50), Some(&50));
+    assert_eq!(base.get(&100), None);
+
+    assert_eq!(inserted.len(), 101);
+    assert_eq!(inserted.get(&0), Some(&42));
+    assert_eq!(removed.len(), 99);
+    assert_eq!(removed.get(&50), None);
+  }
+
+  #[test]
+  fn collisions_are_stored_together() {
+    let map = PersistentMap::new()
+      .insert(key(1, 7), 1)
+      .insert(key(2, 7), 2)
+      .insert(key(3, 7), 3)
+      .insert(key(2, 7), 20);
+
+    assert_eq!(map.len(), 3);
+    assert_eq!(collision_count(&map), 1);
+    assert_eq!(map.get(&key(1, 7)), Some(&1));
+    assert_eq!(map.get(&key(2, 7)), Some(&20));
+    assert_eq!(map.get(&key(3, 7)), Some(&3));
+    assert_eq!(map.get(&key(4, 7)), None);
+  }
+
+  #[test]
+  fn collision_collapses_on_remove() {
+    let map = PersistentMap::new()
+      .insert(key(1, 7), 1)
+      .insert(key(2, 7), 2)
+      .insert(key(3, 8), 3);
+
+    let removed = map.remove(&key(1, 7));
+    assert_eq!(removed.len(), 2);
+    assert_eq!(collision_count(&removed), 0);
+    assert_eq!(removed.get(&key(2, 7)), Some(&2));
+    assert_eq!(removed.get(&key(3, 8)), Some(&3));
+
+    let removed = removed.remove(&key(2, 7)).remove(&key(4, 7));
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed.get(&key(3, 8)), Some(&3));
+
+    assert_eq!(collision_count(&map), 1);
+    assert_eq!(map.len(), 3);
+  }
+
+  #[test]
+  fn insert_beside_collision() {
+    let bucket = neighbour(7);
+
+    let map = PersistentMap::new()
+      .insert(key(1, 7), 1)
+      .insert(key(2, 7), 2)
+      .insert(key(3, bucket), 3);
+
+    assert_eq!(map.len(), 3);
+    assert_eq!(collision_count(&map), 1);
+    assert_eq!(map.get(&key(1, 7)), Some(&1));
+    assert_eq!(map.get(&key(2, 7)), Some(&2));
+    assert_eq!(map.get(&key(3, bucket)), Some(&3));
+
+    let map = map.remove(&key(3, bucket)).remove(&key(2, 7));
+    assert_eq!(map.len(), 1);
+    assert_eq!(collision_count(&map), 0);
+    assert_eq!(map.get(&key(1, 7)), Some(&1));
+  }
+
+  #[test]
+  fn iteration_visits_every_entry_once() {
+    let map: PersistentMap<Colliding, u32> = (0..500)
+      .map(|n| (key(n, (n % 100) as u64), n))
+      .collect();
+
+    let mut ids: Vec<u32> = map.iter().map(|(key, val)| {
+      assert_eq!(key.id, *val);
+      key.id
+    }).collect();
+    ids.sort();
+
+    assert_eq!(ids, (0..500).collect::<Vec<_>>());
+    assert_eq!(map.keys().count(), map.len());
+    assert_eq!(map.values().count(), map.len());
+    assert_eq!(PersistentMap::<u32, u32>::new().iter().count(), 0);
+  }
+}
@@ -0,0 +1,465 @@
+use std::rc::Rc;
+
+const BITS: usize = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+enum Node<T> {
+  Branch(Vec<Rc<Node<T>>>),
+  Leaf(Rc<Vec<T>>),
+}
+
+/// Immutable vector implemented as a 32-way trie with a tail buffer.
+///
+/// Updates copy only the path from the root to the modified leaf, every other
+/// node is shared with the previous version.
+pub struct PersistentVector<T> {
+  len: usize,
+  shift: usize,
+  root: Rc<Node<T>>,
+  tail: Rc<Vec<T>>,
+}
+
+pub struct PersistentVectorIterator<'a, T> {
+  vector: &'a PersistentVector<T>,
+  index: usize,
+  chunk: &'a [T],
+}
+
+impl<T> Clone for PersistentVector<T> {
+  fn clone(&self) -> Self {
+    Self {
+      len: self.len,
+      shift: self.shift,
+      root: self.root.clone(),
+      tail: self.tail.clone(),
+    }
+  }
+}
+
+impl<T: Clone> Default for PersistentVector<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Clone> PersistentVector<T> {
+  pub fn new() -> Self {
+    Self {
+      len: 0,
+      shift: BITS,
+      root: Rc::new(Node::Branch(Vec::new())),
+      tail: Rc::new(Vec::new()),
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Whether both vectors share the same trie and tail.
+  pub fn ptr_eq(&self, other: &Self) -> bool {
+    self.len == other.len
+      && Rc::ptr_eq(&self.root, &other.root)
+      && Rc::ptr_eq(&self.tail, &other.tail)
+  }
+
+  pub fn get(&self, index: usize) -> Option<&T> {
+    if index < self.len {
+      Some(&self.chunk_for(index)[index & MASK])
+    }
+    else {
+      None
+    }
+  }
+
+  pub fn push(&self, val: T) -> Self {
+    if self.len - self.tail_offset() < WIDTH {
+      let mut tail = self.tail.as_ref().clone();
+      tail.push(val);
+
+      return Self {
+        len: self.len + 1,
+        shift: self.shift,
+        root: self.root.clone(),
+        tail: Rc::new(tail),
+      };
+    }
+
+    let tail_node = Rc::new(Node::Leaf(self.tail.clone()));
+
+    let (root, shift) = if (self.len >> BITS) > (1 << self.shift) {
+      let root = Node::Branch(vec![
+        self.root.clone(),
+        Self::new_path(self.shift, tail_node),
+      ]);
+      (Rc::new(root), self.shift + BITS)
+    }
+    else {
+      (self.push_tail(self.shift, &self.root, tail_node), self.shift)
+    };
+
+    Self {
+      len: self.len + 1,
+      shift,
+      root,
+      tail: Rc::new(vec![val]),
+    }
+  }
+
+  pub fn set(&self, index: usize, val: T) -> Option<Self> {
+    if index >= self.len {
+      return None;
+    }
+
+    if index >= self.tail_offset() {
+      let mut tail = self.tail.as_ref().clone();
+      tail[index & MASK] = val;
+
+      return Some(Self {
+        len: self.len,
+        shift: self.shift,
+        root: self.root.clone(),
+        tail: Rc::new(tail),
+      });
+    }
+
+    Some(Self {
+      len: self.len,
+      shift: self.shift,
+      root: Self::set_in(self.shift, &self.root, index, val),
+      tail: self.tail.clone(),
+    })
+  }
+
+  pub fn pop(&self) -> Option<Self> {
+    match self.len {
+      0 => None,
+      1 => Some(Self::new()),
+      _ if self.len - self.tail_offset() > 1 => {
+        let mut tail = self.tail.as_ref().clone();
+        tail.pop();
+
+        Some(Self {
+          len: self.len - 1,
+          shift: self.shift,
+          root: self.root.clone(),
+          tail: Rc::new(tail),
+        })
+      },
+      _ => {
+        let tail = match self.leaf_for(self.len - 2) {
+          Some(leaf) => leaf.clone(),
+          None => unreachable!("tail is not empty"),
+        };
+
+        let mut root = self
+          .pop_tail(self.shift, &self.root)
+          .unwrap_or_else(|| Rc::new(Node::Branch(Vec::new())));
+        let mut shift = self.shift;
+
+        if shift > BITS {
+          if let Node::Branch(children) = root.as_ref() {
+            if children.len() == 1 {
+              root = children[0].clone();
+              shift -= BITS;
+            }
+          }
+        }
+
+        Some(Self {
+          len: self.len - 1,
+          shift,
+          root,
+          tail,
+        })
+      },
+    }
+  }
+
+  pub fn iter(&self) -> PersistentVectorIterator<'_, T> {
+    PersistentVectorIterator {
+      vector: self,
+      index: 0,
+      chunk: &[],
+    }
+  }
+
+  fn tail_offset(&self) -> usize {
+    if self.len < WIDTH {
+      0
+    }
+    else {
+      ((self.len - 1) >> BITS) << BITS
+    }
+  }
+
+  fn leaf_for(&self, index: usize) -> Option<&Rc<Vec<T>>> {
+    let mut node = self.root.as_ref();
+    let mut level = self.shift;
+
+    loop {
+      match node {
+        Node::Branch(children) => {
+          node = children.get((index >> level) & MASK)?.as_ref();
+          level -= BITS;
+        },
+        Node::Leaf(items) => {
+          return Some(items);
+        },
+      }
+    }
+  }
+
+  fn chunk_for(&self, index: usize) -> &[T] {
+    if index >= self.tail_offset() {
+      self.tail.as_slice()
+    }
+    else {
+      match self.leaf_for(index) {
+        Some(leaf) => leaf.as_slice(),
+        None => unreachable!("index is within the trie"),
+      }
+    }
+  }
+
+  fn new_path(level: usize, node: Rc<Node<T>>) -> Rc<Node<T>> {
+    if level == 0 {
+      node
+    }
+    else {
+      Rc::new(Node::Branch(vec![Self::new_path(level - BITS, node)]))
+    }
+  }
+
+  fn push_tail(&self, level: usize, parent: &Rc<Node<T>>, tail_node: Rc<Node<T>>) -> Rc<Node<T>> {
+    let mut children = match parent.as_ref() {
+      Node::Branch(children) => children.clone(),
+      Node::Leaf(..) => unreachable!("leaves are only found at level 0"),
+    };
+
+    let subidx = ((self.len - 1) >> level) & MASK;
+
+    if level == BITS {
+      children.push(tail_node);
+    }
+    else if subidx < children.len() {
+      children[subidx] = self.push_tail(level - BITS, &children[subidx], tail_node);
+    }
+    else {
+      children.push(Self::new_path(level - BITS, tail_node));
+    }
+
+    Rc::new(Node::Branch(children))
+  }
+
+  fn set_in(level: usize, node: &Rc<Node<T>>, index: usize, val: T) -> Rc<Node<T>> {
+    match node.as_ref() {
+      Node::Leaf(items) => {
+        let mut items = items.as_ref().clone();
+        items[index & MASK] = val;
+        Rc::new(Node::Leaf(Rc::new(items)))
+      },
+      Node::Branch(children) => {
+        let mut children = children.clone();
+        let subidx = (index >> level) & MASK;
+        children[subidx] = Self::set_in(level - BITS, &children[subidx], index, val);
+        Rc::new(Node::Branch(children))
+      },
+    }
+  }
+
+  fn pop_tail(&self, level: usize, node: &Rc<Node<T>>) -> Option<Rc<Node<T>>> {
+    let children = match node.as_ref() {
+      Node::Branch(children) => children,
+      Node::Leaf(..) => unreachable!("leaves are only found at level 0"),
+    };
+
+    let subidx = ((self.len - 2) >> level) & MASK;
+
+    if level > BITS {
+      let child = self.pop_tail(level - BITS, &children[subidx]);
+
+      if child.is_none() && subidx == 0 {
+        return None;
+      }
+
+      let mut children = children.clone();
+
+      match child {
+        Some(child) => children[subidx] = child,
+        None => children.truncate(subidx),
+      }
+
+      Some(Rc::new(Node::Branch(children)))
+    }
+    else if subidx == 0 {
+      None
+    }
+    else {
+      let mut children = children.clone();
+      children.truncate(subidx);
+      Some(Rc::new(Node::Branch(children)))
+    }
+  }
+}
+
+impl<T: Clone> FromIterator<T> for PersistentVector<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    iter.into_iter().fold(Self::new(), |vector, val| vector.push(val))
+  }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a PersistentVector<T> {
+  type Item = &'a T;
+  type IntoIter = PersistentVectorIterator<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, T: Clone> Iterator for PersistentVectorIterator<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.index >= self.vector.len {
+      return None;
+    }
+
+    if self.index & MASK == 0 {
+      self.chunk = self.vector.chunk_for(self.index);
+    }
+
+    let item = &self.chunk[self.index & MASK];
+    self.index += 1;
+    Some(item)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn range(len: usize) -> PersistentVector<usize> {
+    (0..len).collect()
+  }
+
+  fn assert_contents(vector: &PersistentVector<usize>, len: usize) {
+    assert_eq!(vector.len(), len);
+    assert_eq!(vector.is_empty(), len == 0);
+    assert_eq!(vector.get(len), None);
+
+    for index in 0..len {
+      assert_eq!(vector.get(index), Some(&index), "index {} of {}", index, len);
+    }
+  }
+
+  #[test]
+  fn push_across_boundaries() {
+    let mut vector = PersistentVector::new();
+
+    for len in 0..=32801 {
+      if matches!(len, 0 | 1 | 31 | 32 | 33 | 1055 | 1056 | 1057 | 32799 | 32800 | 32801) {
+        assert_contents(&vector, len);
+      }
+
+      vector = vector.push(len);
+    }
+  }
+
+  #[test]
+  fn pop_across_boundaries() {
+    let mut vector = range(32801);
+
+    for len in (0..32801).rev() {
+      vector = vector.pop().expect("vector is not empty");
+      assert_eq!(vector.len(), len);
+
+      if len > 0 {
+        assert_eq!(vector.get(len - 1), Some(&(len - 1)));
+      }
+
+      if matches!(len, 0 | 1 | 31 | 32 | 33 | 1055 | 1056 | 1057 | 32799 | 32800) {
+        assert_contents(&vector, len);
+      }
+    }
+
+    assert!(vector.pop().is_none());
+  }
+
+  #[test]
+  fn push_after_pop() {
+    let vector = range(1057).pop().and_then(|vector| vector.pop()).unwrap();
+    let vector = vector.push(1055).push(1056).push(1057);
+    assert_contents(&vector, 1058);
+  }
+
+  #[test]
+  fn set_in_tail() {
+    let vector = range(40);
+    let updated = vector.set(39, 0).unwrap();
+
+    assert_eq!(updated.get(39), Some(&0));
+    assert_eq!(updated.len(), 40);
+    assert!(Rc::ptr_eq(&vector.root, &updated.root));
+    assert_contents(&vector, 40);
+  }
+
+  #[test]
+  fn set_in_trie() {
+    let vector = range(1100);
+    let updated = vector.set(5, 0).unwrap().set(1040, 0).unwrap();
+
+    assert_eq!(updated.get(5), Some(&0));
+    assert_eq!(updated.get(1040), Some(&0));
+    assert_eq!(updated.get(6), Some(&6));
+    assert!(Rc::ptr_eq(&vector.tail, &updated.tail));
+    assert_contents(&vector, 1100);
+  }
+
+  #[test]
+  fn set_out_of_bounds() {
+    assert!(range(0).set(0, 0).is_none());
+    assert!(range(33).set(33, 0).is_none());
+  }
+
+  #[test]
+  fn old_versions_are_unchanged() {
+    let versions: Vec<_> = (0..=70)
+      .scan(PersistentVector::new(), |vector, len| {
+        let current = vector.clone();
+        *vector = vector.push(len);
+        Some(current)
+      })
+      .collect();
+
+    for (len, vector) in versions.iter().enumerate() {
+      assert_contents(vector, len);
+    }
+
+    let popped = versions[70].pop().unwrap();
+    assert_contents(&popped, 69);
+    assert_contents(&versions[70], 70);
+  }
+
+  #[test]
+  fn ptr_eq() {
+    let vector = range(40);
+    assert!(vector.ptr_eq(&vector.clone()));
+    assert!(!vector.ptr_eq(&range(40)));
+    assert!(!vector.ptr_eq(&vector.set(0, 0).unwrap()));
+  }
+
+  #[test]
+  fn iteration() {
+    for len in [0, 1, 32, 33, 1056, 1057] {
+      let items: Vec<usize> = range(len).iter().cloned().collect();
+      assert_eq!(items, (0..len).collect::<Vec<_>>());
+    }
+  }
+}
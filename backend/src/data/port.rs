@@ -0,0 +1,338 @@
+use std::{
+  fs::File,
+  io::{self, BufRead, BufReader, BufWriter, Write},
+  path::Path,
+  rc::Rc,
+  cell::RefCell,
+};
+
+// Input files and stdin keep the character seen by `peek_char`: it is read
+// off the stream, `fill_buf` alone cannot peek a character split across two
+// fills of the buffer.
+enum PortKind {
+  FileInput { reader: BufReader<File>, peeked: Option<char> },
+  FileOutput(BufWriter<File>),
+  Stdin { peeked: Option<char> },
+  Stdout,
+  Stderr,
+  StringInput { buffer: String, offset: usize },
+  StringOutput(String),
+  Closed,
+}
+
+/// Source or sink of characters. Ports are shared: every copy reads from or
+/// writes to the same underlying file, stream or string.
+#[derive(Clone)]
+pub struct Port {
+  name: Rc<str>,
+  kind: Rc<RefCell<PortKind>>,
+}
+
+fn invalid_utf8() -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+}
+
+/// Read one UTF-8 encoded character, continuing past the end of the buffer
+/// when it only holds the start of the character.
+fn read_utf8_char<R: BufRead>(reader: &mut R) -> io::Result<Option<char>> {
+  let width = match reader.fill_buf()?.first() {
+    None => return Ok(None),
+    Some(0x00..=0x7f) => 1,
+    Some(0xc0..=0xdf) => 2,
+    Some(0xe0..=0xef) => 3,
+    Some(_) => 4,
+  };
+
+  let mut bytes = [0; 4];
+
+  reader.read_exact(&mut bytes[..width]).map_err(|err| match err.kind() {
+    io::ErrorKind::UnexpectedEof => invalid_utf8(),
+    _ => err,
+  })?;
+
+  std::str::from_utf8(&bytes[..width])
+    .ok()
+    .and_then(|s| s.chars().next())
+    .map(Some)
+    .ok_or_else(invalid_utf8)
+}
+
+fn peek_char_from<R: BufRead>(reader: &mut R, peeked: &mut Option<char>) -> io::Result<Option<char>> {
+  if peeked.is_none() {
+    *peeked = read_utf8_char(reader)?;
+  }
+
+  Ok(*peeked)
+}
+
+fn read_char_from<R: BufRead>(reader: &mut R, peeked: &mut Option<char>) -> io::Result<Option<char>> {
+  match peeked.take() {
+    Some(c) => Ok(Some(c)),
+    None => read_utf8_char(reader),
+  }
+}
+
+fn read_line_from<R: BufRead>(reader: &mut R, peeked: &mut Option<char>) -> io::Result<Option<String>> {
+  let mut line = String::new();
+  line.extend(peeked.take());
+
+  if !line.ends_with('\n') {
+    reader.read_line(&mut line)?;
+  }
+
+  if line.is_empty() {
+    return Ok(None);
+  }
+
+  if line.ends_with('\n') {
+    line.pop();
+
+    if line.ends_with('\r') {
+      line.pop();
+    }
+  }
+
+  Ok(Some(line))
+}
+
+impl Port {
+  fn new(name: &str, kind: PortKind) -> Self {
+    Self {
+      name: Rc::from(name),
+      kind: Rc::new(RefCell::new(kind)),
+    }
+  }
+
+  pub fn open_input_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+    let file = File::open(path.as_ref())?;
+    let name = path.as_ref().display().to_string();
+    let reader = BufReader::new(file);
+    Ok(Self::new(&name, PortKind::FileInput { reader, peeked: None }))
+  }
+
+  pub fn open_output_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+    let file = File::create(path.as_ref())?;
+    let name = path.as_ref().display().to_string();
+    Ok(Self::new(&name, PortKind::FileOutput(BufWriter::new(file))))
+  }
+
+  pub fn stdin() -> Self {
+    Self::new("stdin", PortKind::Stdin { peeked: None })
+  }
+
+  pub fn stdout() -> Self {
+    Self::new("stdout", PortKind::Stdout)
+  }
+
+  pub fn stderr() -> Self {
+    Self::new("stderr", PortKind::Stderr)
+  }
+
+  pub fn input_string(buffer: String) -> Self {
+    Self::new("string", PortKind::StringInput { buffer, offset: 0 })
+  }
+
+  pub fn output_string() -> Self {
+    Self::new("string", PortKind::StringOutput(String::new()))
+  }
+
+  /// Port failing on every use, such as the default ports of a sandbox.
+  pub fn closed(name: &str) -> Self {
+    Self::new(name, PortKind::Closed)
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn ptr_eq(&self, other: &Self) -> bool {
+    Rc::ptr_eq(&self.kind, &other.kind)
+  }
+
+  pub fn is_input(&self) -> bool {
+    matches!(
+      *self.kind.borrow(),
+      PortKind::FileInput { .. } | PortKind::Stdin { .. } | PortKind::StringInput { .. }
+    )
+  }
+
+  pub fn is_output(&self) -> bool {
+    matches!(
+      *self.kind.borrow(),
+      PortKind::FileOutput(..) | PortKind::Stdout | PortKind::Stderr | PortKind::StringOutput(..)
+    )
+  }
+
+  fn error(&self, detail: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, format!("{} port {}", self.name, detail))
+  }
+
+  /// Read the next character, `None` at end of input.
+  pub fn read_char(&self) -> io::Result<Option<char>> {
+    match &mut *self.kind.borrow_mut() {
+      PortKind::FileInput { reader, peeked } => read_char_from(reader, peeked),
+      PortKind::Stdin { peeked } => read_char_from(&mut io::stdin().lock(), peeked),
+      PortKind::StringInput { buffer, offset } => {
+        let next = buffer[*offset..].chars().next();
+
+        if let Some(c) = next {
+          *offset += c.len_utf8();
+        }
+
+        Ok(next)
+      },
+      PortKind::Closed => Err(self.error("is closed")),
+      _ => Err(self.error("is not an input port")),
+    }
+  }
+
+  /// Look at the next character without consuming it.
+  pub fn peek_char(&self) -> io::Result<Option<char>> {
+    match &mut *self.kind.borrow_mut() {
+      PortKind::FileInput { reader, peeked } => peek_char_from(reader, peeked),
+      PortKind::Stdin { peeked } => peek_char_from(&mut io::stdin().lock(), peeked),
+      PortKind::StringInput { buffer, offset } => Ok(buffer[*offset..].chars().next()),
+      PortKind::Closed => Err(self.error("is closed")),
+      _ => Err(self.error("is not an input port")),
+    }
+  }
+
+  /// Read the source text of the next datum, skipping whitespace and
+  /// comments before it, `None` at end of input. The text is only delimited
+  /// here, parsing is left to the frontend.
+  pub fn read_datum(&self) -> io::Result<Option<String>> {
+    loop {
+      match self.peek_char()? {
+        None => return Ok(None),
+        Some(';') => {
+          self.read_line()?;
+        },
+        Some(c) if c.is_whitespace() => {
+          self.read_char()?;
+        },
+        Some(_) => break,
+      }
+    }
+
+    let mut text = String::new();
+    let mut depth = 0usize;
+
+    while let Some(c) = self.peek_char()? {
+      let delimiter = c.is_whitespace() || "()[]{}\";".contains(c);
+
+      if depth == 0 && !text.is_empty() && delimiter {
+        break;
+      }
+
+      self.read_char()?;
+      text.push(c);
+
+      match c {
+        '(' | '[' | '{' => depth += 1,
+        ')' | ']' | '}' => {
+          depth = depth.saturating_sub(1);
+
+          if depth == 0 {
+            break;
+          }
+        },
+        '"' => {
+          while let Some(c) = self.read_char()? {
+            text.push(c);
+
+            match c {
+              '"' => break,
+              '\\' => text.extend(self.read_char()?),
+              _ => {},
+            }
+          }
+
+          if depth == 0 {
+            break;
+          }
+        },
+        ';' => {
+          text.pop();
+          self.read_line()?;
+          text.push('\n');
+        },
+        '#' if self.peek_char()? == Some('\\') => {
+          text.extend(self.read_char()?);
+          text.extend(self.read_char()?);
+        },
+        '#' if (depth > 0 || text.len() == 1) && self.peek_char()? == Some('(') => {
+          text.extend(self.read_char()?);
+          depth += 1;
+        },
+        _ => {},
+      }
+    }
+
+    Ok(Some(text))
+  }
+
+  /// Read the next line without its terminator, `None` at end of input.
+  pub fn read_line(&self) -> io::Result<Option<String>> {
+    match &mut *self.kind.borrow_mut() {
+      PortKind::FileInput { reader, peeked } => read_line_from(reader, peeked),
+      PortKind::Stdin { peeked } => read_line_from(&mut io::stdin().lock(), peeked),
+      PortKind::StringInput { buffer, offset } => {
+        let mut remaining = &buffer.as_bytes()[*offset..];
+        let line = read_line_from(&mut remaining, &mut None)?;
+        *offset = buffer.len() - remaining.len();
+        Ok(line)
+      },
+      PortKind::Closed => Err(self.error("is closed")),
+      _ => Err(self.error("is not an input port")),
+    }
+  }
+
+  /// Read everything left, an empty string at end of input.
+  pub fn read_to_string(&self) -> io::Result<String> {
+    let mut result = String::new();
+
+    while let Some(c) = self.read_char()? {
+      result.push(c);
+    }
+
+    Ok(result)
+  }
+
+  pub fn write_str(&self, s: &str) -> io::Result<()> {
+    match &mut *self.kind.borrow_mut() {
+      PortKind::FileOutput(writer) => writer.write_all(s.as_bytes()),
+      PortKind::Stdout => {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(s.as_bytes())?;
+        stdout.flush()
+      },
+      PortKind::Stderr => io::stderr().lock().write_all(s.as_bytes()),
+      PortKind::StringOutput(buffer) => {
+        buffer.push_str(s);
+        Ok(())
+      },
+      PortKind::Closed => Err(self.error("is closed")),
+      _ => Err(self.error("is not an output port")),
+    }
+  }
+
+  /// Text written so far to a string output port.
+  pub fn output(&self) -> io::Result<String> {
+    match &*self.kind.borrow() {
+      PortKind::StringOutput(buffer) => Ok(buffer.clone()),
+      _ => Err(self.error("is not a string output port")),
+    }
+  }
+
+  /// Flush pending output and release the underlying file. Closing a port
+  /// twice is allowed, using a closed port is an error.
+  pub fn close(&self) -> io::Result<()> {
+    let kind = std::mem::replace(&mut *self.kind.borrow_mut(), PortKind::Closed);
+
+    match kind {
+      PortKind::FileOutput(mut writer) => writer.flush(),
+      PortKind::Stdout => io::stdout().flush(),
+      _ => Ok(()),
+    }
+  }
+}
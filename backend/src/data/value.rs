@@ -1,20 +1,52 @@
+use std::{
+  collections::HashMap,
+  hash::{Hash, Hasher},
+  rc::Rc,
+  cell::RefCell,
+};
 use lispers_common::Symbol;
 
 use crate::prelude::*;
-use super::{List, Function};
+use super::{List, Function, PersistentVector, PersistentMap, Port};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Type {
   Boolean,
   Integer,
   Float,
+  Char,
   String,
   Symbol,
+  Keyword,
   List,
+  Vector,
+  Map,
+  PersistentVector,
+  PersistentMap,
   Function,
+  Port,
 }
 
 impl Type {
+  pub fn name(&self) -> &'static str {
+    match self {
+      Type::Boolean => "boolean",
+      Type::Integer => "integer",
+      Type::Float => "float",
+      Type::Char => "char",
+      Type::String => "string",
+      Type::Symbol => "symbol",
+      Type::Keyword => "keyword",
+      Type::List => "list",
+      Type::Vector => "vector",
+      Type::Map => "map",
+      Type::PersistentVector => "persistent-vector",
+      Type::PersistentMap => "persistent-map",
+      Type::Function => "function",
+      Type::Port => "port",
+    }
+  }
+
   pub fn error(got: Type, expected: Type) -> RuntimeError {
     RuntimeError::TypeError {
       expected: format!("{:?}", expected),
@@ -23,7 +55,7 @@ impl Type {
   }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Sym<S: Symbol>(S);
 
 impl<S: Symbol> Sym<S> {
@@ -49,10 +81,17 @@ pub enum Value<S: Symbol> {
   Boolean(bool),
   Integer(i64),
   Float(f64),
+  Char(char),
   String(String),
   Symbol(Sym<S>),
+  Keyword(Sym<S>),
   List(List<S>),
+  Vector(Rc<RefCell<Vec<Value<S>>>>),
+  Map(Rc<HashMap<Value<S>, Value<S>>>),
+  PersistentVector(PersistentVector<Value<S>>),
+  PersistentMap(PersistentMap<Value<S>, Value<S>>),
   Function(Function<S>),
+  Port(Port),
 }
 
 impl<S: Symbol> Default for Value<S> {
@@ -67,10 +106,202 @@ impl<S: Symbol> Value<S> {
       Value::Boolean(..) => Type::Boolean,
       Value::Integer(..) => Type::Integer,
       Value::Float(..) => Type::Float,
+      Value::Char(..) => Type::Char,
       Value::String(..) => Type::String,
       Value::Symbol(..) => Type::Symbol,
+      Value::Keyword(..) => Type::Keyword,
       Value::List(..) => Type::List,
+      Value::Vector(..) => Type::Vector,
+      Value::Map(..) => Type::Map,
+      Value::PersistentVector(..) => Type::PersistentVector,
+      Value::PersistentMap(..) => Type::PersistentMap,
       Value::Function(..) => Type::Function,
+      Value::Port(..) => Type::Port,
+    }
+  }
+
+  /// Only `false` and the empty list are falsy, every other value (including
+  /// `0` and `""`) is truthy.
+  pub fn is_truthy(&self) -> bool {
+    match self {
+      Value::Boolean(val) => *val,
+      Value::List(list) => !list.empty(),
+      _ => true,
+    }
+  }
+
+  /// Only immutable scalar values can be used as map keys.
+  pub fn is_hashable(&self) -> bool {
+    matches!(
+      self,
+      Value::Boolean(..)
+      | Value::Integer(..)
+      | Value::Char(..)
+      | Value::String(..)
+      | Value::Symbol(..)
+      | Value::Keyword(..)
+    )
+  }
+
+  /// Identity, as tested by `eq?`: booleans, integers, characters, symbols,
+  /// keywords and the empty list compare by value, every other value only
+  /// equals itself (the same cons cell, collection or function). Floats and
+  /// strings are copied whenever they are used, so they have no identity to
+  /// compare and fall back to `eqv?`.
+  pub fn is_identical(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Value::Float(..), Value::Float(..)) => self.is_eqv(other),
+      (Value::String(..), Value::String(..)) => self.is_eqv(other),
+      (Value::Boolean(a), Value::Boolean(b)) => a == b,
+      (Value::Integer(a), Value::Integer(b)) => a == b,
+      (Value::Char(a), Value::Char(b)) => a == b,
+      (Value::Symbol(a), Value::Symbol(b)) => a == b,
+      (Value::Keyword(a), Value::Keyword(b)) => a == b,
+      (Value::List(a), Value::List(b)) => a.ptr_eq(b),
+      (Value::Vector(a), Value::Vector(b)) => Rc::ptr_eq(a, b),
+      (Value::Map(a), Value::Map(b)) => Rc::ptr_eq(a, b),
+      (Value::PersistentVector(a), Value::PersistentVector(b)) => a.ptr_eq(b),
+      (Value::PersistentMap(a), Value::PersistentMap(b)) => a.ptr_eq(b),
+      (Value::Function(a), Value::Function(b)) => a == b,
+      (Value::Port(a), Value::Port(b)) => a.ptr_eq(b),
+      _ => false,
+    }
+  }
+
+  /// Equivalence, as tested by `eqv?`: floats and strings compare by value,
+  /// everything else by identity. Floats are compared bit for bit, so
+  /// `(eqv? nan nan)` holds while `(eqv? 0.0 -0.0)` does not.
+  pub fn is_eqv(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Value::Float(a), Value::Float(b)) => a.to_bits() == b.to_bits(),
+      (Value::String(a), Value::String(b)) => a == b,
+      _ => self.is_identical(other),
+    }
+  }
+
+  pub fn assert_hashable(&self) -> Result<()> {
+    if self.is_hashable() {
+      Ok(())
+    }
+    else {
+      Err(RuntimeError::TypeError {
+        expected: "Hashable".to_string(),
+        got: format!("{:?}", self.as_type()),
+      })
+    }
+  }
+  /// Check the keys of every map nested in a literal. Map literals keep
+  /// their keys unevaluated, so that `{[x y] :point}` can be a pattern, and
+  /// only once quoted or read may a mutable key escape. Lists are allowed:
+  /// they are key expressions and cons cells are never mutated.
+  pub fn assert_hashable_keys(&self) -> Result<()> {
+    match self {
+      Value::List(list) => list.into_iter().try_for_each(|item| item.assert_hashable_keys()),
+      Value::Vector(items) => items.borrow().iter().try_for_each(Value::assert_hashable_keys),
+      Value::Map(map) => map.iter().try_for_each(|(key, val)| {
+        if !matches!(key, Value::List(..)) {
+          key.assert_hashable()?;
+        }
+
+        key.assert_hashable_keys()?;
+        val.assert_hashable_keys()
+      }),
+      _ => Ok(()),
+    }
+  }
+
+}
+
+/// Structural equality, as tested by `equal?` and `=`. This is the single
+/// source of truth for comparing values: lists, vectors and maps are compared
+/// element by element (lengths included), mutable and persistent collections
+/// compare equal when they hold the same elements, and functions fall back
+/// to identity.
+impl<S: Symbol> PartialEq for Value<S> {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Value::Boolean(a), Value::Boolean(b)) => a == b,
+      (Value::Integer(a), Value::Integer(b)) => a == b,
+      (Value::Float(a), Value::Float(b)) => a == b,
+      (Value::Char(a), Value::Char(b)) => a == b,
+      (Value::String(a), Value::String(b)) => a == b,
+      (Value::Symbol(a), Value::Symbol(b)) => a == b,
+      (Value::Keyword(a), Value::Keyword(b)) => a == b,
+      (Value::List(a), Value::List(b)) => {
+        let mut iter_a = a.into_iter();
+        let mut iter_b = b.into_iter();
+
+        loop {
+          match (iter_a.next(), iter_b.next()) {
+            (None, None) => break true,
+            (Some(item_a), Some(item_b)) if item_a == item_b => {},
+            _ => break false,
+          }
+        }
+      },
+      (Value::Vector(a), Value::Vector(b)) => *a.borrow() == *b.borrow(),
+      (Value::PersistentVector(a), Value::PersistentVector(b)) => {
+        a.len() == b.len() && a.iter().eq(b.iter())
+      },
+      (Value::Vector(a), Value::PersistentVector(b))
+      | (Value::PersistentVector(b), Value::Vector(a)) => {
+        let a = a.borrow();
+        a.len() == b.len() && a.iter().eq(b.iter())
+      },
+      (Value::Map(a), Value::Map(b)) => a == b,
+      (Value::PersistentMap(a), Value::PersistentMap(b)) => {
+        a.len() == b.len() && a.iter().all(|(key, val)| b.get(key) == Some(val))
+      },
+      (Value::Map(a), Value::PersistentMap(b))
+      | (Value::PersistentMap(b), Value::Map(a)) => {
+        a.len() == b.len() && a.iter().all(|(key, val)| b.get(key) == Some(val))
+      },
+      (Value::Function(a), Value::Function(b)) => a == b,
+      (Value::Port(a), Value::Port(b)) => a.ptr_eq(b),
+      _ => false,
+    }
+  }
+}
+
+/// Map keys are restricted to hashable values (see `Value::is_hashable`), for
+/// which equality is reflexive.
+impl<S: Symbol> Eq for Value<S> {}
+
+impl<S: Symbol> Hash for Value<S> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    match self {
+      Value::Boolean(val) => (Type::Boolean, val).hash(state),
+      Value::Integer(val) => (Type::Integer, val).hash(state),
+      Value::Float(val) => (Type::Float, val.to_bits()).hash(state),
+      Value::Char(val) => (Type::Char, val).hash(state),
+      Value::String(val) => (Type::String, val).hash(state),
+      Value::Symbol(sym) => (Type::Symbol, sym).hash(state),
+      Value::Keyword(sym) => (Type::Keyword, sym).hash(state),
+      Value::List(list) => {
+        Type::List.hash(state);
+
+        for item in list.into_iter() {
+          item.hash(state);
+        }
+      },
+      Value::Vector(items) => {
+        Type::Vector.hash(state);
+
+        for item in items.borrow().iter() {
+          item.hash(state);
+        }
+      },
+      Value::PersistentVector(items) => {
+        Type::Vector.hash(state);
+
+        for item in items.iter() {
+          item.hash(state);
+        }
+      },
+      Value::Map(map) => (Type::Map, map.len()).hash(state),
+      Value::PersistentMap(map) => (Type::Map, map.len()).hash(state),
+      Value::Function(..) => Type::Function.hash(state),
+      Value::Port(..) => Type::Port.hash(state),
     }
   }
 }
@@ -108,6 +339,17 @@ impl<S: Symbol> TryFrom<Value<S>> for f64 {
   }
 }
 
+impl<S: Symbol> TryFrom<Value<S>> for char {
+  type Error = RuntimeError;
+
+  fn try_from(value: Value<S>) -> std::result::Result<Self, Self::Error> {
+    match value {
+      Value::Char(val) => Ok(val),
+      _ => Err(Type::error(value.as_type(), Type::Char)),
+    }
+  }
+}
+
 impl<S: Symbol> TryFrom<Value<S>> for String {
   type Error = RuntimeError;
 
@@ -141,6 +383,50 @@ impl<S: Symbol> TryFrom<Value<S>> for List<S> {
   }
 }
 
+impl<S: Symbol> TryFrom<Value<S>> for Rc<RefCell<Vec<Value<S>>>> {
+  type Error = RuntimeError;
+
+  fn try_from(value: Value<S>) -> std::result::Result<Self, Self::Error> {
+    match value {
+      Value::Vector(val) => Ok(val),
+      _ => Err(Type::error(value.as_type(), Type::Vector)),
+    }
+  }
+}
+
+impl<S: Symbol> TryFrom<Value<S>> for Rc<HashMap<Value<S>, Value<S>>> {
+  type Error = RuntimeError;
+
+  fn try_from(value: Value<S>) -> std::result::Result<Self, Self::Error> {
+    match value {
+      Value::Map(val) => Ok(val),
+      _ => Err(Type::error(value.as_type(), Type::Map)),
+    }
+  }
+}
+
+impl<S: Symbol> TryFrom<Value<S>> for PersistentVector<Value<S>> {
+  type Error = RuntimeError;
+
+  fn try_from(value: Value<S>) -> std::result::Result<Self, Self::Error> {
+    match value {
+      Value::PersistentVector(val) => Ok(val),
+      _ => Err(Type::error(value.as_type(), Type::PersistentVector)),
+    }
+  }
+}
+
+impl<S: Symbol> TryFrom<Value<S>> for PersistentMap<Value<S>, Value<S>> {
+  type Error = RuntimeError;
+
+  fn try_from(value: Value<S>) -> std::result::Result<Self, Self::Error> {
+    match value {
+      Value::PersistentMap(val) => Ok(val),
+      _ => Err(Type::error(value.as_type(), Type::PersistentMap)),
+    }
+  }
+}
+
 impl<S: Symbol> TryFrom<Value<S>> for Function<S> {
   type Error = RuntimeError;
 
@@ -185,6 +471,17 @@ impl<S: Symbol> TryFrom<&Value<S>> for f64 {
   }
 }
 
+impl<S: Symbol> TryFrom<&Value<S>> for char {
+  type Error = RuntimeError;
+
+  fn try_from(value: &Value<S>) -> std::result::Result<Self, Self::Error> {
+    match value {
+      Value::Char(val) => Ok(*val),
+      _ => Err(Type::error(value.as_type(), Type::Char)),
+    }
+  }
+}
+
 impl<S: Symbol> TryFrom<&Value<S>> for String {
   type Error = RuntimeError;
 
@@ -218,6 +515,50 @@ impl<S: Symbol> TryFrom<&Value<S>> for List<S> {
   }
 }
 
+impl<S: Symbol> TryFrom<&Value<S>> for Rc<RefCell<Vec<Value<S>>>> {
+  type Error = RuntimeError;
+
+  fn try_from(value: &Value<S>) -> std::result::Result<Self, Self::Error> {
+    match value {
+      Value::Vector(val) => Ok(val.clone()),
+      _ => Err(Type::error(value.as_type(), Type::Vector)),
+    }
+  }
+}
+
+impl<S: Symbol> TryFrom<&Value<S>> for Rc<HashMap<Value<S>, Value<S>>> {
+  type Error = RuntimeError;
+
+  fn try_from(value: &Value<S>) -> std::result::Result<Self, Self::Error> {
+    match value {
+      Value::Map(val) => Ok(val.clone()),
+      _ => Err(Type::error(value.as_type(), Type::Map)),
+    }
+  }
+}
+
+impl<S: Symbol> TryFrom<&Value<S>> for PersistentVector<Value<S>> {
+  type Error = RuntimeError;
+
+  fn try_from(value: &Value<S>) -> std::result::Result<Self, Self::Error> {
+    match value {
+      Value::PersistentVector(val) => Ok(val.clone()),
+      _ => Err(Type::error(value.as_type(), Type::PersistentVector)),
+    }
+  }
+}
+
+impl<S: Symbol> TryFrom<&Value<S>> for PersistentMap<Value<S>, Value<S>> {
+  type Error = RuntimeError;
+
+  fn try_from(value: &Value<S>) -> std::result::Result<Self, Self::Error> {
+    match value {
+      Value::PersistentMap(val) => Ok(val.clone()),
+      _ => Err(Type::error(value.as_type(), Type::PersistentMap)),
+    }
+  }
+}
+
 impl<S: Symbol> TryFrom<&Value<S>> for Function<S> {
   type Error = RuntimeError;
 
@@ -228,3 +569,14 @@ impl<S: Symbol> TryFrom<&Value<S>> for Function<S> {
     }
   }
 }
+
+impl<S: Symbol> TryFrom<&Value<S>> for Port {
+  type Error = RuntimeError;
+
+  fn try_from(value: &Value<S>) -> std::result::Result<Self, Self::Error> {
+    match value {
+      Value::Port(val) => Ok(val.clone()),
+      _ => Err(Type::error(value.as_type(), Type::Port)),
+    }
+  }
+}
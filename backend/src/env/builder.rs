@@ -0,0 +1,86 @@
+use std::{collections::HashSet, rc::Rc, cell::RefCell};
+use lispers_common::{Backend, Symbol};
+
+use crate::data::Context;
+use crate::interpreter::Interpreter;
+use super::{Env, Capability};
+
+/// Builds envs exposing only some capability groups. Calling a builtin of a
+/// missing group fails with `PermissionDenied`.
+///
+/// ```ignore
+/// let env = EnvBuilder::restricted()
+///   .with(Capability::Arithmetic)
+///   .build(&mut interpreter);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EnvBuilder {
+  capabilities: HashSet<Capability>,
+  prelude: bool,
+}
+
+impl Default for EnvBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl EnvBuilder {
+  /// Every capability and the prelude, as `Interpreter::standard_env`.
+  pub fn new() -> Self {
+    Self {
+      capabilities: Capability::ALL.into_iter().collect(),
+      prelude: true,
+    }
+  }
+
+  /// No capability, only the builtins outside of every group and the
+  /// prelude.
+  pub fn restricted() -> Self {
+    Self {
+      capabilities: HashSet::new(),
+      prelude: true,
+    }
+  }
+
+  pub fn with(mut self, capability: Capability) -> Self {
+    self.capabilities.insert(capability);
+    self
+  }
+
+  pub fn without(mut self, capability: Capability) -> Self {
+    self.capabilities.remove(&capability);
+    self
+  }
+
+  /// Whether to evaluate the Lisp prelude in the env, see
+  /// `Interpreter::standard_env`.
+  pub fn prelude(mut self, prelude: bool) -> Self {
+    self.prelude = prelude;
+    self
+  }
+
+  pub fn build<S: Symbol, B: Backend<S>>(
+    &self,
+    interpreter: &mut Interpreter<S, B>,
+  ) -> Rc<RefCell<Env<S>>> {
+    let env = interpreter.bare_env();
+
+    for capability in Capability::ALL {
+      if self.capabilities.contains(&capability) {
+        continue;
+      }
+
+      for name in capability.builtins() {
+        let sym = interpreter.intern(name);
+        env.borrow_mut().deny(sym, capability);
+      }
+    }
+
+    if self.prelude {
+      interpreter.load_prelude(env.clone());
+    }
+
+    env
+  }
+}
@@ -0,0 +1,69 @@
+/// Group of builtins an env may be denied, see `EnvBuilder`. Builtins
+/// outside of every group, such as list, string or map primitives, are
+/// always available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+  /// Integer and float arithmetic: `+`, `-`, `*`, `/` and their float
+  /// counterparts.
+  Arithmetic,
+  /// Standard streams: `println`, `read-line`, `write-string`...
+  Io,
+  /// Process control: `exit`.
+  Process,
+  /// File system: opening files, `load` and `import`.
+  Fs,
+  /// Wall clock: `current-time`.
+  Time,
+}
+
+impl Capability {
+  pub const ALL: [Capability; 5] = [
+    Capability::Arithmetic,
+    Capability::Io,
+    Capability::Process,
+    Capability::Fs,
+    Capability::Time,
+  ];
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      Capability::Arithmetic => "arithmetic",
+      Capability::Io => "io",
+      Capability::Process => "process",
+      Capability::Fs => "fs",
+      Capability::Time => "time",
+    }
+  }
+
+  /// Natives and special forms requiring this capability.
+  pub fn builtins(&self) -> &'static [&'static str] {
+    match self {
+      Capability::Arithmetic => &["+", "-", "*", "/", ".+", ".-", ".*", "./"],
+      Capability::Io => &[
+        "println",
+        "current-input-port",
+        "current-output-port",
+        "current-error-port",
+        "read-line",
+        "read-char",
+        "write-string",
+        "close-port",
+      ],
+      Capability::Process => &["exit"],
+      Capability::Fs => &[
+        "open-input-file",
+        "open-output-file",
+        "call-with-input-file",
+        "load",
+        "import",
+      ],
+      Capability::Time => &["current-time"],
+    }
+  }
+}
+
+impl std::fmt::Display for Capability {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
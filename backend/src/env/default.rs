@@ -1,8 +1,22 @@
 use lispers_common::{StringInterner, Backend, Symbol};
-use crate::data::{Value, Function};
+use crate::data::{Value, Function, NativeFn};
 use super::{Env, primitives};
 
 
+fn define_native<S, B>(
+  env: &mut Env<S>,
+  interner: &mut StringInterner<B>,
+  name: &str,
+  func: NativeFn<S>,
+)
+  where
+    S: Symbol,
+    B: Backend<S>
+{
+  let name = interner.get_or_intern(name);
+  env.define(name, Value::Function(Function::NativeFn { name, func }));
+}
+
 pub fn default_env<S, B>(interner: &mut StringInterner<B>) -> Env<S>
   where
     S: Symbol,
@@ -10,82 +24,128 @@ pub fn default_env<S, B>(interner: &mut StringInterner<B>) -> Env<S>
 {
   let mut env = Env::new();
 
-  env.define(
-    interner.get_or_intern("+"),
-    Value::Function(Function::NativeFn(primitives::arithmetic::iadd)),
-  );
-  env.define(
-    interner.get_or_intern("-"),
-    Value::Function(Function::NativeFn(primitives::arithmetic::isub)),
-  );
-  env.define(
-    interner.get_or_intern("*"),
-    Value::Function(Function::NativeFn(primitives::arithmetic::imul)),
-  );
-  env.define(
-    interner.get_or_intern("/"),
-    Value::Function(Function::NativeFn(primitives::arithmetic::idiv)),
-  );
-  env.define(
-    interner.get_or_intern(".+"),
-    Value::Function(Function::NativeFn(primitives::arithmetic::fadd)),
-  );
-  env.define(
-    interner.get_or_intern(".-"),
-    Value::Function(Function::NativeFn(primitives::arithmetic::fsub)),
-  );
-  env.define(
-    interner.get_or_intern(".*"),
-    Value::Function(Function::NativeFn(primitives::arithmetic::fmul)),
-  );
-  env.define(
-    interner.get_or_intern("./"),
-    Value::Function(Function::NativeFn(primitives::arithmetic::fdiv)),
-  );
-  env.define(
-    interner.get_or_intern("<"),
-    Value::Function(Function::NativeFn(primitives::comparison::ilt)),
-  );
-  env.define(
-    interner.get_or_intern("<="),
-    Value::Function(Function::NativeFn(primitives::comparison::ilte)),
-  );
-  env.define(
-    interner.get_or_intern(">="),
-    Value::Function(Function::NativeFn(primitives::comparison::igte)),
-  );
-  env.define(
-    interner.get_or_intern(">"),
-    Value::Function(Function::NativeFn(primitives::comparison::igt)),
-  );
-  env.define(
-    interner.get_or_intern(".<"),
-    Value::Function(Function::NativeFn(primitives::comparison::flt)),
-  );
-  env.define(
-    interner.get_or_intern(".<="),
-    Value::Function(Function::NativeFn(primitives::comparison::flte)),
-  );
-  env.define(
-    interner.get_or_intern(".>="),
-    Value::Function(Function::NativeFn(primitives::comparison::fgte)),
-  );
-  env.define(
-    interner.get_or_intern(".>"),
-    Value::Function(Function::NativeFn(primitives::comparison::fgt)),
-  );
-  env.define(
-    interner.get_or_intern("="),
-    Value::Function(Function::NativeFn(primitives::comparison::eq)),
-  );
-  env.define(
-    interner.get_or_intern("!="),
-    Value::Function(Function::NativeFn(primitives::comparison::ne)),
-  );
-  env.define(
-    interner.get_or_intern("exit"),
-    Value::Function(Function::NativeFn(primitives::proc::exit)),
-  );
+  define_native(&mut env, interner, "+", primitives::arithmetic::iadd);
+  define_native(&mut env, interner, "-", primitives::arithmetic::isub);
+  define_native(&mut env, interner, "*", primitives::arithmetic::imul);
+  define_native(&mut env, interner, "/", primitives::arithmetic::idiv);
+  define_native(&mut env, interner, ".+", primitives::arithmetic::fadd);
+  define_native(&mut env, interner, ".-", primitives::arithmetic::fsub);
+  define_native(&mut env, interner, ".*", primitives::arithmetic::fmul);
+  define_native(&mut env, interner, "./", primitives::arithmetic::fdiv);
+  define_native(&mut env, interner, "<", primitives::comparison::ilt);
+  define_native(&mut env, interner, "<=", primitives::comparison::ilte);
+  define_native(&mut env, interner, ">=", primitives::comparison::igte);
+  define_native(&mut env, interner, ">", primitives::comparison::igt);
+  define_native(&mut env, interner, ".<", primitives::comparison::flt);
+  define_native(&mut env, interner, ".<=", primitives::comparison::flte);
+  define_native(&mut env, interner, ".>=", primitives::comparison::fgte);
+  define_native(&mut env, interner, ".>", primitives::comparison::fgt);
+  define_native(&mut env, interner, "=", primitives::comparison::eq);
+  define_native(&mut env, interner, "!=", primitives::comparison::ne);
+  define_native(&mut env, interner, "eq?", primitives::comparison::is_eq);
+  define_native(&mut env, interner, "eqv?", primitives::comparison::is_eqv);
+  define_native(&mut env, interner, "equal?", primitives::comparison::is_equal);
+  define_native(&mut env, interner, "not", primitives::comparison::not);
+  define_native(&mut env, interner, "list", primitives::list::list);
+  define_native(&mut env, interner, "cons", primitives::list::cons);
+  define_native(&mut env, interner, "car", primitives::list::car);
+  define_native(&mut env, interner, "cdr", primitives::list::cdr);
+  define_native(&mut env, interner, "null?", primitives::list::is_null);
+  define_native(&mut env, interner, "length", primitives::list::length);
+  define_native(&mut env, interner, "append", primitives::list::append);
+  define_native(&mut env, interner, "reverse", primitives::list::reverse);
+  define_native(&mut env, interner, "nth", primitives::list::nth);
+  define_native(&mut env, interner, "last", primitives::list::last);
+  define_native(&mut env, interner, "take", primitives::list::take);
+  define_native(&mut env, interner, "drop", primitives::list::drop);
+  define_native(&mut env, interner, "member", primitives::list::member);
+  define_native(&mut env, interner, "list-copy", primitives::list::list_copy);
+  define_native(&mut env, interner, "map", primitives::higher_order::map);
+  define_native(&mut env, interner, "for-each", primitives::higher_order::for_each);
+  define_native(&mut env, interner, "filter", primitives::higher_order::filter);
+  define_native(&mut env, interner, "reduce", primitives::higher_order::reduce);
+  define_native(&mut env, interner, "fold-left", primitives::higher_order::fold_left);
+  define_native(&mut env, interner, "fold-right", primitives::higher_order::fold_right);
+  define_native(&mut env, interner, "apply", primitives::higher_order::apply);
+  define_native(&mut env, interner, "sort", primitives::higher_order::sort);
+  define_native(&mut env, interner, "any?", primitives::higher_order::any);
+  define_native(&mut env, interner, "every?", primitives::higher_order::every);
+  define_native(&mut env, interner, "type-of", primitives::types::type_of);
+  define_native(&mut env, interner, "boolean?", primitives::types::is_boolean);
+  define_native(&mut env, interner, "integer?", primitives::types::is_integer);
+  define_native(&mut env, interner, "float?", primitives::types::is_float);
+  define_native(&mut env, interner, "number?", primitives::types::is_number);
+  define_native(&mut env, interner, "char?", primitives::types::is_char);
+  define_native(&mut env, interner, "string?", primitives::types::is_string);
+  define_native(&mut env, interner, "symbol?", primitives::types::is_symbol);
+  define_native(&mut env, interner, "keyword?", primitives::types::is_keyword);
+  define_native(&mut env, interner, "list?", primitives::types::is_list);
+  define_native(&mut env, interner, "pair?", primitives::types::is_pair);
+  define_native(&mut env, interner, "vector?", primitives::types::is_vector);
+  define_native(&mut env, interner, "map?", primitives::types::is_map);
+  define_native(&mut env, interner, "procedure?", primitives::types::is_procedure);
+  define_native(&mut env, interner, "port?", primitives::types::is_port);
+  define_native(&mut env, interner, "arity", primitives::types::arity);
+  define_native(&mut env, interner, "procedure-name", primitives::types::procedure_name);
+  define_native(&mut env, interner, "vector", primitives::vector::vector);
+  define_native(&mut env, interner, "vector-ref", primitives::vector::vector_ref);
+  define_native(&mut env, interner, "vector-set!", primitives::vector::vector_set);
+  define_native(&mut env, interner, "vector-length", primitives::vector::vector_length);
+  define_native(&mut env, interner, "vector-push!", primitives::vector::vector_push);
+  define_native(&mut env, interner, "vector->list", primitives::vector::vector_to_list);
+  define_native(&mut env, interner, "list->vector", primitives::vector::list_to_vector);
+  define_native(&mut env, interner, "hash-map", primitives::map::hash_map);
+  define_native(&mut env, interner, "get", primitives::map::get);
+  define_native(&mut env, interner, "assoc", primitives::map::assoc);
+  define_native(&mut env, interner, "dissoc", primitives::map::dissoc);
+  define_native(&mut env, interner, "contains?", primitives::map::contains);
+  define_native(&mut env, interner, "keys", primitives::map::keys);
+  define_native(&mut env, interner, "vals", primitives::map::vals);
+  define_native(&mut env, interner, "map->list", primitives::map::map_to_list);
+  define_native(&mut env, interner, "persistent-vector", primitives::vector::persistent_vector);
+  define_native(&mut env, interner, "list->persistent-vector", primitives::vector::list_to_persistent_vector);
+  define_native(&mut env, interner, "conj", primitives::vector::conj);
+  define_native(&mut env, interner, "pop", primitives::vector::pop);
+  define_native(&mut env, interner, "persistent-hash-map", primitives::map::persistent_hash_map);
+  define_native(&mut env, interner, "string-length", primitives::string::string_length);
+  define_native(&mut env, interner, "substring", primitives::string::substring);
+  define_native(&mut env, interner, "string-append", primitives::string::string_append);
+  define_native(&mut env, interner, "string-split", primitives::string::string_split);
+  define_native(&mut env, interner, "string-join", primitives::string::string_join);
+  define_native(&mut env, interner, "string-upcase", primitives::string::string_upcase);
+  define_native(&mut env, interner, "string-downcase", primitives::string::string_downcase);
+  define_native(&mut env, interner, "string-index", primitives::string::string_index);
+  define_native(&mut env, interner, "string->list", primitives::string::string_to_list);
+  define_native(&mut env, interner, "list->string", primitives::string::list_to_string);
+  define_native(&mut env, interner, "string->number", primitives::string::string_to_number);
+  define_native(&mut env, interner, "number->string", primitives::string::number_to_string);
+  define_native(&mut env, interner, "string-trim", primitives::string::string_trim);
+  define_native(&mut env, interner, "string-contains?", primitives::string::string_contains);
+  define_native(&mut env, interner, "string-replace", primitives::string::string_replace);
+  define_native(&mut env, interner, "char->integer", primitives::string::char_to_integer);
+  define_native(&mut env, interner, "integer->char", primitives::string::integer_to_char);
+  define_native(&mut env, interner, "open-input-file", primitives::io::open_input_file);
+  define_native(&mut env, interner, "open-output-file", primitives::io::open_output_file);
+  define_native(&mut env, interner, "open-input-string", primitives::io::open_input_string);
+  define_native(&mut env, interner, "open-output-string", primitives::io::open_output_string);
+  define_native(&mut env, interner, "get-output-string", primitives::io::get_output_string);
+  define_native(&mut env, interner, "current-input-port", primitives::io::current_input_port);
+  define_native(&mut env, interner, "current-output-port", primitives::io::current_output_port);
+  define_native(&mut env, interner, "current-error-port", primitives::io::current_error_port);
+  define_native(&mut env, interner, "read-line", primitives::io::read_line);
+  define_native(&mut env, interner, "read-char", primitives::io::read_char);
+  define_native(&mut env, interner, "read", primitives::io::read);
+  define_native(&mut env, interner, "display", primitives::io::display);
+  define_native(&mut env, interner, "write", primitives::io::write);
+  define_native(&mut env, interner, "write-string", primitives::io::write_string);
+  define_native(&mut env, interner, "format", primitives::format::format);
+  define_native(&mut env, interner, "json-parse", primitives::json::json_parse);
+  define_native(&mut env, interner, "json-stringify", primitives::json::json_stringify);
+  define_native(&mut env, interner, "close-port", primitives::io::close_port);
+  define_native(&mut env, interner, "with-output-to-string", primitives::io::with_output_to_string);
+  define_native(&mut env, interner, "call-with-input-file", primitives::io::call_with_input_file);
+  define_native(&mut env, interner, "exit", primitives::proc::exit);
+  define_native(&mut env, interner, "current-time", primitives::time::current_time);
 
   env
 }
@@ -8,11 +8,20 @@ use crate::data::Value;
 
 mod primitives;
 mod default;
+mod capability;
+mod builder;
 pub use default::default_env;
+pub use capability::Capability;
+pub use builder::EnvBuilder;
+
+/// Lisp source of the standard library, see `Interpreter::standard_env`.
+pub const PRELUDE: &str = include_str!("prelude.lisp");
 
 pub struct Env<S: Symbol> {
   parent: Option<Rc<RefCell<Env<S>>>>,
   values: HashMap<S, Value<S>>,
+  /// Builtins removed by `EnvBuilder`, with the capability they require.
+  denied: HashMap<S, Capability>,
 }
 
 impl<S: Symbol> Env<S> {
@@ -20,6 +29,7 @@ impl<S: Symbol> Env<S> {
     Self {
       parent: None,
       values: HashMap::new(),
+      denied: HashMap::new(),
     }
   }
 
@@ -27,6 +37,7 @@ impl<S: Symbol> Env<S> {
     Self {
       parent: Some(parent),
       values: HashMap::new(),
+      denied: HashMap::new(),
     }
   }
 
@@ -34,6 +45,30 @@ impl<S: Symbol> Env<S> {
     self.values.insert(symbol, value);
   }
 
+  /// Symbols defined in this scope, excluding its parents.
+  pub fn symbols(&self) -> Vec<S> {
+    self.values.keys().copied().collect()
+  }
+
+  /// Remove a builtin, using it then fails with `PermissionDenied`.
+  pub fn deny(&mut self, symbol: S, capability: Capability) {
+    self.values.remove(&symbol);
+    self.denied.insert(symbol, capability);
+  }
+
+  /// Capability required by a denied builtin.
+  pub fn denied(&self, symbol: S) -> Option<Capability> {
+    match self.denied.get(&symbol) {
+      Some(capability) => Some(*capability),
+      None => self.parent.as_ref().and_then(|parent| parent.borrow().denied(symbol)),
+    }
+  }
+
+  pub fn allows(&self, capability: Capability) -> bool {
+    !self.denied.values().any(|denied| *denied == capability)
+      && self.parent.as_ref().is_none_or(|parent| parent.borrow().allows(capability))
+  }
+
   pub fn undefine(&mut self, symbol: S) {
     if self.values.contains_key(&symbol) {
       self.values.remove(&symbol);
@@ -56,8 +91,8 @@ impl<S: Symbol> Env<S> {
   }
 
   pub fn set(&mut self, symbol: S, value: Value<S>) -> Result<(), S> {
-    if self.values.contains_key(&symbol) {
-      self.values.insert(symbol, value);
+    if let Some(slot) = self.values.get_mut(&symbol) {
+      *slot = value;
       Ok(())
     }
     else if let Some(parent) = &self.parent {
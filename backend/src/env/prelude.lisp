@@ -0,0 +1,107 @@
+; Standard prelude, evaluated by `Interpreter::standard_env`.
+;
+; Everything here is written in terms of the native primitives. Functions
+; that need to be fast or that interact with the host belong in
+; `env/primitives` instead.
+
+; List accessors
+
+(def first (lambda (lst) (car lst)))
+(def second (lambda (lst) (car (cdr lst))))
+(def third (lambda (lst) (car (cdr (cdr lst)))))
+(def rest (lambda (lst) (cdr lst)))
+
+(def caar (lambda (lst) (car (car lst))))
+(def cadr (lambda (lst) (car (cdr lst))))
+(def cdar (lambda (lst) (cdr (car lst))))
+(def cddr (lambda (lst) (cdr (cdr lst))))
+(def caddr (lambda (lst) (car (cdr (cdr lst)))))
+
+; Functions
+
+(def identity (lambda (x) x))
+
+(def constantly
+  (lambda (x)
+    (lambda (&rest _) x)))
+
+(def complement
+  (lambda (pred)
+    (lambda (&rest args) (not (apply pred args)))))
+
+(def partial
+  (lambda (f &rest bound)
+    (lambda (&rest args) (apply f (append bound args)))))
+
+; `((compose f g) x)` is `(f (g x))`.
+(def compose
+  (lambda (&rest fs)
+    (lambda (x)
+      (fold-right (lambda (f acc) (f acc)) x fs))))
+
+; Numbers
+
+(def inc (lambda (n) (+ n 1)))
+(def dec (lambda (n) (- n 1)))
+
+(def zero? (lambda (n) (= n 0)))
+(def positive? (lambda (n) (> n 0)))
+(def negative? (lambda (n) (< n 0)))
+(def even? (lambda (n) (= n (* 2 (/ n 2)))))
+(def odd? (lambda (n) (not (even? n))))
+
+(def abs (lambda (n) (if (< n 0) (- 0 n) n)))
+
+(def min
+  (lambda (n &rest ns)
+    (reduce (lambda (a b) (if (< b a) b a)) n ns)))
+
+(def max
+  (lambda (n &rest ns)
+    (reduce (lambda (a b) (if (> b a) b a)) n ns)))
+
+(def sum (lambda (lst) (reduce + 0 lst)))
+(def product (lambda (lst) (reduce * 1 lst)))
+
+; `(range end)` or `(range start end)`, counting up from `start` (0 by
+; default) and excluding `end`.
+(def range
+  (lambda (a &optional b)
+    (do ((i (dec (if b b a)) (dec i))
+         (acc () (cons i acc)))
+        ((< i (if b a 0)) acc))))
+
+; Lists
+
+(def remove
+  (lambda (pred lst)
+    (filter (complement pred) lst)))
+
+(def count
+  (lambda (pred lst)
+    (length (filter pred lst))))
+
+; The first element satisfying `pred`, or `false`.
+(def find
+  (lambda (pred lst)
+    (dolist (x lst false)
+      (if (pred x) (break x)))))
+
+(def zip
+  (lambda (&rest lists)
+    (apply map list lists)))
+
+; Walks an explicit stack of the lists left to visit, so deep or long lists
+; don't exhaust the native stack.
+(def flatten
+  (lambda (x)
+    (let ((stack (list x))
+          (acc ()))
+      (while (not (null? stack))
+        (let ((top (car stack)))
+          (set! stack (cdr stack))
+          (cond
+            ((null? top) ())
+            ((list? top) (set! stack (cons (car top) (cons (cdr top) stack))))
+            (else (set! acc (cons top acc))))))
+      (reverse acc))))
@@ -2,12 +2,13 @@ use std::{rc::Rc, cell::RefCell};
 use lispers_common::Symbol;
 
 use crate::prelude::*;
-use crate::data::Value;
+use crate::data::{Value, Context};
 use crate::env::Env;
 
 use crate::utils::assert_at_least_args;
 
 pub fn iadd<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -24,6 +25,7 @@ pub fn iadd<S: Symbol>(
 }
 
 pub fn isub<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -41,6 +43,7 @@ pub fn isub<S: Symbol>(
 }
 
 pub fn imul<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -57,6 +60,7 @@ pub fn imul<S: Symbol>(
 }
 
 pub fn idiv<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -74,6 +78,7 @@ pub fn idiv<S: Symbol>(
 }
 
 pub fn fadd<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -90,6 +95,7 @@ pub fn fadd<S: Symbol>(
 }
 
 pub fn fsub<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -107,6 +113,7 @@ pub fn fsub<S: Symbol>(
 }
 
 pub fn fmul<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -123,6 +130,7 @@ pub fn fmul<S: Symbol>(
 }
 
 pub fn fdiv<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -2,12 +2,13 @@ use std::{rc::Rc, cell::RefCell};
 use lispers_common::Symbol;
 
 use crate::prelude::*;
-use crate::data::Value;
+use crate::data::{Value, Context};
 use crate::env::Env;
 
-use crate::utils::assert_at_least_args;
+use crate::utils::{assert_exactly_args, assert_at_least_args};
 
 pub fn ilt<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -30,6 +31,7 @@ pub fn ilt<S: Symbol>(
 }
 
 pub fn ilte<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -52,6 +54,7 @@ pub fn ilte<S: Symbol>(
 }
 
 pub fn igte<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -74,6 +77,7 @@ pub fn igte<S: Symbol>(
 }
 
 pub fn igt<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -97,6 +101,7 @@ pub fn igt<S: Symbol>(
 
 
 pub fn flt<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -119,6 +124,7 @@ pub fn flt<S: Symbol>(
 }
 
 pub fn flte<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -141,6 +147,7 @@ pub fn flte<S: Symbol>(
 }
 
 pub fn fgte<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -163,6 +170,7 @@ pub fn fgte<S: Symbol>(
 }
 
 pub fn fgt<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -184,47 +192,66 @@ pub fn fgt<S: Symbol>(
   Ok(Value::Boolean(result))
 }
 
+/// `=` is variadic `equal?`: numbers compare by value but not across types,
+/// so `(= 1 1.0)` is `false`.
 pub fn eq<S: Symbol>(
-  env: Rc<RefCell<Env<S>>>,
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
   assert_at_least_args(2, args.len())?;
 
-  let mut result = true;
-  let mut prev_arg = None;
-
-  for arg in args {
-    if let Some(prev_arg) = prev_arg {
-      result = match (&prev_arg, &arg) {
-        (Value::Boolean(a), Value::Boolean(b)) => a == b,
-        (Value::Integer(a), Value::Integer(b)) => a == b,
-        (Value::Float(a), Value::Float(b)) => a == b,
-        (Value::String(a), Value::String(b)) => a == b,
-        (Value::Symbol(a), Value::Symbol(b)) => a.as_symbol() == b.as_symbol(),
-        (Value::List(a), Value::List(b)) => {
-          let mut matching = true;
-
-          for (item_a, item_b) in std::iter::zip(a.into_iter(), b.into_iter()) {
-            let item_eq: bool = eq(env.clone(), vec![item_a, item_b])?.try_into()?;
-            matching &= item_eq;
-          }
-
-          matching
-        },
-        _ => false,
-      };
-    }
-
-    prev_arg = Some(arg);
-  }
-
+  let result = args.windows(2).all(|pair| pair[0] == pair[1]);
   Ok(Value::Boolean(result))
 }
 
 pub fn ne<S: Symbol>(
+  ctx: &mut dyn Context<S>,
   env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
-  let equal: bool = eq(env.clone(), args)?.try_into()?;
+  let equal: bool = eq(ctx, env.clone(), args)?.try_into()?;
   Ok(Value::Boolean(!equal))
 }
+
+/// `(eq? a b)` tests identity. Every pair that is `eq?` is also `eqv?`.
+pub fn is_eq<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+  Ok(Value::Boolean(args[0].is_identical(&args[1])))
+}
+
+/// `(eqv? a b)` extends `eq?` to floats and strings. Every pair that is
+/// `eqv?` is also `equal?`, except NaN which is `eqv?` to itself but not `=`.
+pub fn is_eqv<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+  Ok(Value::Boolean(args[0].is_eqv(&args[1])))
+}
+
+/// `(equal? a b)` compares structurally, descending into lists, vectors and
+/// maps. It is the two-argument form of `=`.
+pub fn is_equal<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+  Ok(Value::Boolean(args[0] == args[1]))
+}
+
+/// `(not x)` is `true` for the falsy values `false` and `()`.
+pub fn not<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(!args[0].is_truthy()))
+}
@@ -0,0 +1,248 @@
+use std::{rc::Rc, cell::RefCell, iter::Peekable, str::Chars};
+use lispers_common::Symbol;
+
+use crate::prelude::*;
+use crate::data::{Value, Type, Port, Context};
+use crate::env::{Env, Capability};
+
+use crate::utils::{assert_at_least_args, assert_capability};
+
+// Directives follow Common Lisp: `~[params][:][@]<char>`, where params are
+// comma-separated integers or `'c` characters.
+//
+//  - `~a` displays and `~s` writes the next argument, `~mincol,padchar a`
+//    pads on the right (on the left with `@`);
+//  - `~d`, `~x`, `~o` and `~b` print an integer in base 10, 16, 8 and 2,
+//    `~mincol,padchar d` pads on the left, `@` always prints the sign;
+//  - `~width,decimals f` prints a float (or an integer) with fixed decimals;
+//  - `~%` is a newline and `~~` a tilde.
+
+#[derive(Clone, Copy)]
+enum Param {
+  Int(i64),
+  Char(char),
+}
+
+struct Directive {
+  params: Vec<Option<Param>>,
+  at: bool,
+  kind: char,
+}
+
+impl Directive {
+  fn int(&self, index: usize) -> Result<Option<usize>> {
+    match self.params.get(index).copied().flatten() {
+      None => Ok(None),
+      Some(Param::Int(n)) if n >= 0 => Ok(Some(n as usize)),
+      Some(_) => Err(self.error("expects a non-negative integer parameter")),
+    }
+  }
+
+  fn char(&self, index: usize) -> Result<char> {
+    match self.params.get(index).copied().flatten() {
+      None => Ok(' '),
+      Some(Param::Char(c)) => Ok(c),
+      Some(_) => Err(self.error("expects a character parameter such as '0")),
+    }
+  }
+
+  fn error(&self, detail: &str) -> RuntimeError {
+    RuntimeError::FormatError {
+      detail: format!("directive ~{} {}", self.kind, detail),
+    }
+  }
+}
+
+fn parse_directive(chars: &mut Peekable<Chars>) -> Result<Directive> {
+  let mut params = Vec::new();
+  let mut current = None;
+  let mut digits = String::new();
+
+  loop {
+    match chars.peek().copied() {
+      Some(c) if c.is_ascii_digit() || (c == '-' && digits.is_empty()) => {
+        digits.push(c);
+        chars.next();
+      },
+      Some('\'') => {
+        chars.next();
+        current = chars.next().map(Param::Char);
+      },
+      Some(',') => {
+        chars.next();
+        params.push(current.take().or(parse_int(&digits)?));
+        digits.clear();
+      },
+      _ => break,
+    }
+  }
+
+  let last = current.take().or(parse_int(&digits)?);
+
+  if last.is_some() || !params.is_empty() {
+    params.push(last);
+  }
+
+  let mut at = false;
+
+  while let Some(c @ (':' | '@')) = chars.peek().copied() {
+    at |= c == '@';
+    chars.next();
+  }
+
+  let kind = chars
+    .next()
+    .ok_or_else(|| RuntimeError::FormatError { detail: "unterminated directive ~".to_string() })?
+    .to_ascii_lowercase();
+
+  Ok(Directive { params, at, kind })
+}
+
+fn parse_int(digits: &str) -> Result<Option<Param>> {
+  if digits.is_empty() {
+    return Ok(None);
+  }
+
+  digits
+    .parse()
+    .map(|n| Some(Param::Int(n)))
+    .map_err(|_| RuntimeError::FormatError { detail: format!("invalid parameter {}", digits) })
+}
+
+fn pad(s: String, mincol: Option<usize>, padchar: char, left: bool) -> String {
+  let width = s.chars().count();
+
+  match mincol {
+    Some(mincol) if mincol > width => {
+      let padding: String = std::iter::repeat_n(padchar, mincol - width).collect();
+
+      if left {
+        padding + &s
+      }
+      else {
+        s + &padding
+      }
+    },
+    _ => s,
+  }
+}
+
+fn format_integer(n: i64, radix: u32, sign: bool) -> String {
+  let digits = match radix {
+    16 => format!("{:x}", n.unsigned_abs()),
+    8 => format!("{:o}", n.unsigned_abs()),
+    2 => format!("{:b}", n.unsigned_abs()),
+    _ => n.unsigned_abs().to_string(),
+  };
+
+  match (n < 0, sign) {
+    (true, _) => format!("-{}", digits),
+    (false, true) => format!("+{}", digits),
+    (false, false) => digits,
+  }
+}
+
+/// Expand `control` with `args`, every argument must be consumed.
+fn expand<S: Symbol>(ctx: &mut dyn Context<S>, control: &str, args: &[Value<S>]) -> Result<String> {
+  let mut output = String::new();
+  let mut chars = control.chars().peekable();
+  let mut args = args.iter();
+
+  while let Some(c) = chars.next() {
+    if c != '~' {
+      output.push(c);
+      continue;
+    }
+
+    let directive = parse_directive(&mut chars)?;
+
+    let mut next_arg = || {
+      args.next().ok_or_else(|| directive.error("has no argument left"))
+    };
+
+    let expansion = match directive.kind {
+      '%' => "\n".to_string(),
+      '~' => "~".to_string(),
+      'a' | 's' => {
+        let arg = next_arg()?;
+        let repr = match directive.kind {
+          'a' => ctx.display(arg),
+          _ => ctx.write(arg),
+        };
+
+        pad(repr, directive.int(0)?, directive.char(1)?, directive.at)
+      },
+      'd' | 'x' | 'o' | 'b' => {
+        let radix = match directive.kind {
+          'x' => 16,
+          'o' => 8,
+          'b' => 2,
+          _ => 10,
+        };
+
+        let n: i64 = next_arg()?.try_into()?;
+        let repr = format_integer(n, radix, directive.at);
+        pad(repr, directive.int(0)?, directive.char(1)?, true)
+      },
+      'f' => {
+        let n = match next_arg()? {
+          Value::Float(n) => *n,
+          Value::Integer(n) => *n as f64,
+          val => return Err(Type::error(val.as_type(), Type::Float)),
+        };
+
+        let repr = match directive.int(1)? {
+          Some(decimals) => format!("{:.*}", decimals, n),
+          None => format!("{}", n),
+        };
+
+        let repr = if directive.at && n >= 0.0 { format!("+{}", repr) } else { repr };
+        pad(repr, directive.int(0)?, directive.char(2)?, true)
+      },
+      _ => return Err(directive.error("is not supported")),
+    };
+
+    output.push_str(&expansion);
+  }
+
+  match args.len() {
+    0 => Ok(output),
+    unused => Err(RuntimeError::FormatError {
+      detail: format!("{} argument(s) left unused by \"{}\"", unused, control),
+    }),
+  }
+}
+
+/// `(format control arg ...)` returns the expanded string.
+/// `(format dest control arg ...)` prints it instead: `true` writes to the
+/// current output port, a port writes there, `false` returns the string.
+pub fn format<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_at_least_args(1, args.len())?;
+
+  let (dest, control, rest) = match &args[0] {
+    Value::String(control) => (None, control.clone(), &args[1..]),
+    dest => {
+      assert_at_least_args(2, args.len())?;
+      let control: String = (&args[1]).try_into()?;
+      (Some(dest.clone()), control, &args[2..])
+    },
+  };
+
+  let output = expand(ctx, &control, rest)?;
+
+  let port = match dest {
+    None | Some(Value::Boolean(false)) => return Ok(Value::String(output)),
+    Some(Value::Boolean(true)) => {
+      assert_capability(&env, Capability::Io, "format to the current output port")?;
+      ctx.output_port()
+    },
+    Some(port) => Port::try_from(&port)?,
+  };
+
+  port.write_str(&output)?;
+  Ok(Value::default())
+}
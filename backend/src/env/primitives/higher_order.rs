@@ -0,0 +1,280 @@
+use std::{rc::Rc, cell::RefCell, cmp::Ordering};
+use lispers_common::Symbol;
+
+use crate::prelude::*;
+use crate::data::{Value, Type, List, Context};
+use crate::env::Env;
+
+use crate::utils::{assert_exactly_args, assert_at_least_args, assert_args_between};
+
+fn lists<S: Symbol>(args: &[Value<S>]) -> Result<Vec<Vec<Value<S>>>> {
+  let mut lists = Vec::with_capacity(args.len());
+
+  for arg in args {
+    let list: List<S> = arg.try_into()?;
+    lists.push(list.into_iter().collect());
+  }
+
+  Ok(lists)
+}
+
+/// Arguments for the n-th call when walking several lists in parallel.
+fn nth_args<S: Symbol>(lists: &[Vec<Value<S>>], index: usize) -> Vec<Value<S>> {
+  lists.iter().map(|list| list[index].clone()).collect()
+}
+
+fn shortest<S: Symbol>(lists: &[Vec<Value<S>>]) -> usize {
+  lists.iter().map(|list| list.len()).min().unwrap_or(0)
+}
+
+pub fn map<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_at_least_args(2, args.len())?;
+
+  let func = args[0].clone();
+  let lists = lists(&args[1..])?;
+  let mut results = Vec::new();
+
+  for index in 0..shortest(&lists) {
+    let result = ctx.apply(env.clone(), func.clone(), nth_args(&lists, index))?;
+    results.push(result);
+  }
+
+  Ok(Value::List(results.into_iter().collect()))
+}
+
+pub fn for_each<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_at_least_args(2, args.len())?;
+
+  let func = args[0].clone();
+  let lists = lists(&args[1..])?;
+
+  for index in 0..shortest(&lists) {
+    ctx.apply(env.clone(), func.clone(), nth_args(&lists, index))?;
+  }
+
+  Ok(Value::default())
+}
+
+pub fn filter<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let pred = args[0].clone();
+  let list: List<S> = (&args[1]).try_into()?;
+  let mut results = Vec::new();
+
+  for item in list.into_iter() {
+    let keep = ctx.apply(env.clone(), pred.clone(), vec![item.clone()])?;
+
+    if keep.is_truthy() {
+      results.push(item);
+    }
+  }
+
+  Ok(Value::List(results.into_iter().collect()))
+}
+
+/// `(reduce f list)` or `(reduce f init list)`, calling `(f acc item)`.
+pub fn reduce<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(2, Some(3), args.len())?;
+
+  let func = args[0].clone();
+  let list: List<S> = args[args.len() - 1].clone().try_into()?;
+  let mut items = list.into_iter();
+
+  let mut acc = match args.len() {
+    3 => args[1].clone(),
+    _ => items.next().ok_or_else(|| RuntimeError::NilValue {
+      detail: "reduce called on empty list without initial value".to_string(),
+    })?,
+  };
+
+  for item in items {
+    acc = ctx.apply(env.clone(), func.clone(), vec![acc, item])?;
+  }
+
+  Ok(acc)
+}
+
+pub fn fold_left<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(3, args.len())?;
+
+  let func = args[0].clone();
+  let list: List<S> = (&args[2]).try_into()?;
+  let mut acc = args[1].clone();
+
+  for item in list.into_iter() {
+    acc = ctx.apply(env.clone(), func.clone(), vec![acc, item])?;
+  }
+
+  Ok(acc)
+}
+
+pub fn fold_right<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(3, args.len())?;
+
+  let func = args[0].clone();
+  let list: List<S> = (&args[2]).try_into()?;
+  let items: Vec<Value<S>> = list.into_iter().collect();
+  let mut acc = args[1].clone();
+
+  for item in items.into_iter().rev() {
+    acc = ctx.apply(env.clone(), func.clone(), vec![item, acc])?;
+  }
+
+  Ok(acc)
+}
+
+/// `(apply f arg ... list)` calls `f` with the leading arguments followed by
+/// the elements of the final list.
+pub fn apply<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_at_least_args(2, args.len())?;
+
+  let func = args[0].clone();
+  let spread: List<S> = (&args[args.len() - 1]).try_into()?;
+
+  let mut call_args: Vec<Value<S>> = args[1..args.len() - 1].to_vec();
+  call_args.extend(&spread);
+
+  ctx.apply(env, func, call_args)
+}
+
+fn natural_less<S: Symbol>(a: &Value<S>, b: &Value<S>) -> Result<bool> {
+  let ordering = match (a, b) {
+    (Value::Integer(a), Value::Integer(b)) => a.partial_cmp(b),
+    (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+    (Value::Char(a), Value::Char(b)) => a.partial_cmp(b),
+    (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+    _ => None,
+  };
+
+  ordering
+    .map(|ordering| ordering == Ordering::Less)
+    .ok_or_else(|| Type::error(b.as_type(), a.as_type()))
+}
+
+type LessFn<'a, S> = dyn FnMut(&Value<S>, &Value<S>) -> Result<bool> + 'a;
+
+/// Stable merge sort with a comparator that may fail.
+fn merge_sort<S: Symbol>(
+  mut items: Vec<Value<S>>,
+  less: &mut LessFn<'_, S>,
+) -> Result<Vec<Value<S>>> {
+  if items.len() <= 1 {
+    return Ok(items);
+  }
+
+  let right = items.split_off(items.len() / 2);
+  let left = merge_sort(items, less)?;
+  let right = merge_sort(right, less)?;
+
+  let mut merged = Vec::with_capacity(left.len() + right.len());
+  let mut left = left.into_iter().peekable();
+  let mut right = right.into_iter().peekable();
+
+  while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+    if less(r, l)? {
+      merged.extend(right.next());
+    }
+    else {
+      merged.extend(left.next());
+    }
+  }
+
+  merged.extend(left);
+  merged.extend(right);
+  Ok(merged)
+}
+
+/// `(sort list)` or `(sort list less?)`.
+pub fn sort<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(1, Some(2), args.len())?;
+
+  let list: List<S> = (&args[0]).try_into()?;
+  let items: Vec<Value<S>> = list.into_iter().collect();
+
+  let sorted = match args.get(1) {
+    None => merge_sort(items, &mut natural_less)?,
+    Some(less) => {
+      merge_sort(items, &mut |a, b| {
+        ctx.apply(env.clone(), less.clone(), vec![a.clone(), b.clone()])
+          .map(|less| less.is_truthy())
+      })?
+    },
+  };
+
+  Ok(Value::List(sorted.into_iter().collect()))
+}
+
+pub fn any<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let pred = args[0].clone();
+  let list: List<S> = (&args[1]).try_into()?;
+
+  for item in list.into_iter() {
+    let found = ctx.apply(env.clone(), pred.clone(), vec![item])?;
+
+    if found.is_truthy() {
+      return Ok(Value::Boolean(true));
+    }
+  }
+
+  Ok(Value::Boolean(false))
+}
+
+pub fn every<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let pred = args[0].clone();
+  let list: List<S> = (&args[1]).try_into()?;
+
+  for item in list.into_iter() {
+    let ok = ctx.apply(env.clone(), pred.clone(), vec![item])?;
+
+    if !ok.is_truthy() {
+      return Ok(Value::Boolean(false));
+    }
+  }
+
+  Ok(Value::Boolean(true))
+}
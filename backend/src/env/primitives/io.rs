@@ -0,0 +1,255 @@
+use std::{rc::Rc, cell::RefCell};
+use lispers_common::Symbol;
+
+use crate::prelude::*;
+use crate::data::{Value, Port, Context};
+use crate::env::{Env, Capability};
+
+use crate::utils::{assert_exactly_args, assert_args_between, assert_capability};
+
+// I/O failures, including the use of a closed port, surface as
+// `RuntimeError::IOError`. Reading past the end of input yields nil.
+
+fn input_port<S: Symbol>(
+  ctx: &dyn Context<S>,
+  args: &[Value<S>],
+  index: usize,
+) -> Result<Port> {
+  match args.get(index) {
+    Some(port) => port.try_into(),
+    None => Ok(ctx.input_port()),
+  }
+}
+
+pub fn open_input_file<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let path: String = (&args[0]).try_into()?;
+  Ok(Value::Port(Port::open_input_file(path)?))
+}
+
+pub fn open_output_file<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let path: String = (&args[0]).try_into()?;
+  Ok(Value::Port(Port::open_output_file(path)?))
+}
+
+pub fn open_input_string<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  Ok(Value::Port(Port::input_string(s)))
+}
+
+pub fn open_output_string<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(0, args.len())?;
+  Ok(Value::Port(Port::output_string()))
+}
+
+pub fn get_output_string<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let port: Port = (&args[0]).try_into()?;
+  Ok(Value::String(port.output()?))
+}
+
+pub fn current_input_port<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(0, args.len())?;
+  Ok(Value::Port(ctx.input_port()))
+}
+
+pub fn current_output_port<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(0, args.len())?;
+  Ok(Value::Port(ctx.output_port()))
+}
+
+pub fn current_error_port<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(0, args.len())?;
+  Ok(Value::Port(Port::stderr()))
+}
+
+/// `(read-line [port])` reads from the current input port by default.
+pub fn read_line<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(0, Some(1), args.len())?;
+
+  let port = input_port(ctx, &args, 0)?;
+  Ok(port.read_line()?.map(Value::String).unwrap_or_default())
+}
+
+/// `(read-char [port])` reads from the current input port by default.
+pub fn read_char<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(0, Some(1), args.len())?;
+
+  let port = input_port(ctx, &args, 0)?;
+  Ok(port.read_char()?.map(Value::Char).unwrap_or_default())
+}
+
+/// `(read [source])` parses the next datum from a string or a port (the
+/// current input port by default) without evaluating it.
+pub fn read<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(0, Some(1), args.len())?;
+
+  let port = match args.first() {
+    Some(Value::String(s)) => Port::input_string(s.clone()),
+    None => {
+      assert_capability(&env, Capability::Io, "read from the current input port")?;
+      ctx.input_port()
+    },
+    _ => input_port(ctx, &args, 0)?,
+  };
+
+  let text = port.read_datum()?;
+
+  match text {
+    Some(text) => Ok(ctx.read(&text)?.into_iter().next().unwrap_or_default()),
+    None => Ok(Value::default()),
+  }
+}
+
+/// Without a port, `display` and `write` return the representation as a
+/// string. With one, they write it there.
+fn print<S: Symbol>(args: &[Value<S>], repr: String) -> Result<Value<S>> {
+  match args.get(1) {
+    Some(port) => {
+      let port: Port = port.try_into()?;
+      port.write_str(&repr)?;
+      Ok(Value::default())
+    },
+    None => Ok(Value::String(repr)),
+  }
+}
+
+/// `(display x [port])`, the way `println` prints values.
+pub fn display<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(1, Some(2), args.len())?;
+
+  let repr = ctx.display(&args[0]);
+  print(&args, repr)
+}
+
+/// `(write x [port])`, quoting strings and characters so that `read` gives
+/// back an `equal?` value.
+pub fn write<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(1, Some(2), args.len())?;
+
+  let repr = ctx.write(&args[0]);
+  print(&args, repr)
+}
+
+/// `(write-string s [port])` writes to the current output port by default.
+pub fn write_string<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(1, Some(2), args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  let port = match args.get(1) {
+    Some(port) => port.try_into()?,
+    None => ctx.output_port(),
+  };
+
+  port.write_str(&s)?;
+  Ok(Value::default())
+}
+
+pub fn close_port<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let port: Port = (&args[0]).try_into()?;
+  port.close()?;
+  Ok(Value::default())
+}
+
+/// `(with-output-to-string thunk)` calls `thunk` with the current output
+/// redirected to a string, which is returned.
+pub fn with_output_to_string<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let port = Port::output_string();
+  let previous = ctx.replace_output_port(port.clone());
+  let result = ctx.apply(env, args[0].clone(), vec![]);
+  ctx.replace_output_port(previous);
+
+  result?;
+  Ok(Value::String(port.output()?))
+}
+
+/// `(call-with-input-file path proc)` calls `proc` with a port reading
+/// `path`, closing it afterwards.
+pub fn call_with_input_file<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let path: String = (&args[0]).try_into()?;
+  let port = Port::open_input_file(path)?;
+  let result = ctx.apply(env, args[1].clone(), vec![Value::Port(port.clone())]);
+  port.close()?;
+
+  result
+}
@@ -0,0 +1,604 @@
+use std::{
+  collections::HashMap,
+  iter::Peekable,
+  str::Chars,
+  rc::Rc,
+  cell::RefCell,
+};
+use lispers_common::Symbol;
+
+use crate::prelude::*;
+use crate::data::{Value, Context};
+use crate::env::Env;
+
+use crate::utils::{assert_at_least_args, assert_pairs};
+
+// JSON objects decode to hash maps (duplicate keys are errors), arrays to
+// vectors, `null` to nil. Integers that fit in 64 bits decode to `Integer`,
+// other numbers to `Float`. Numbers beyond the range of `f64` are errors.
+//
+// Encoding accepts lists and vectors as arrays, maps with string or keyword
+// keys as objects (keys are sorted, so the output is stable, and must be
+// unique once keywords are turned into strings), keywords and characters as
+// strings, and nil as `null`.
+
+/// Trailing `:name value` options given to the JSON builtins.
+fn option<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  options: &[Value<S>],
+  name: &str,
+) -> Result<Option<Value<S>>> {
+  let name = ctx.intern(name);
+  assert_pairs(options.len())?;
+
+  for pair in options.chunks(2) {
+    match pair {
+      [Value::Keyword(key), val] if key.as_symbol() == name => {
+        return Ok(Some(val.clone()));
+      },
+      [Value::Keyword(..), _] => {},
+      [val, ..] => return Err(RuntimeError::TypeError {
+        expected: "KeywordArgument".to_string(),
+        got: format!("{:?}", val.as_type()),
+      }),
+      [] => unreachable!(),
+    }
+  }
+
+  Ok(None)
+}
+
+/// Arrays and objects nested deeper than this are rejected, rather than
+/// overflowing the native stack.
+const MAX_DEPTH: usize = 512;
+
+struct Parser<'a> {
+  chars: Peekable<Chars<'a>>,
+  line: usize,
+  column: usize,
+  keywords: bool,
+  /// Arrays and objects currently open.
+  depth: usize,
+}
+
+impl<'a> Parser<'a> {
+  fn new(input: &'a str, keywords: bool) -> Self {
+    Self {
+      chars: input.chars().peekable(),
+      line: 1,
+      column: 1,
+      keywords,
+      depth: 0,
+    }
+  }
+
+  /// Error located at the next unread character.
+  fn error(&self, detail: &str) -> RuntimeError {
+    RuntimeError::JsonError {
+      detail: format!("{} at line {}, column {}", detail, self.line, self.column),
+    }
+  }
+
+  fn unexpected(&mut self) -> RuntimeError {
+    match self.peek() {
+      None => self.error("unexpected end of input"),
+      Some(c) => self.error(&format!("unexpected character {:?}", c)),
+    }
+  }
+
+  fn peek(&mut self) -> Option<char> {
+    self.chars.peek().copied()
+  }
+
+  fn next(&mut self) -> Option<char> {
+    let c = self.chars.next()?;
+
+    if c == '\n' {
+      self.line += 1;
+      self.column = 1;
+    }
+    else {
+      self.column += 1;
+    }
+
+    Some(c)
+  }
+
+  fn expect(&mut self, expected: char) -> Result<()> {
+    match self.peek() {
+      Some(c) if c == expected => {
+        self.next();
+        Ok(())
+      },
+      _ => Err(self.unexpected()),
+    }
+  }
+
+  fn skip_whitespace(&mut self) {
+    while let Some(' ' | '\t' | '\n' | '\r') = self.peek() {
+      self.next();
+    }
+  }
+
+  fn parse_document<S: Symbol>(&mut self, ctx: &mut dyn Context<S>) -> Result<Value<S>> {
+    let value = self.parse_value(ctx)?;
+    self.skip_whitespace();
+
+    match self.peek() {
+      None => Ok(value),
+      Some(_) => Err(self.unexpected()),
+    }
+  }
+
+  fn parse_value<S: Symbol>(&mut self, ctx: &mut dyn Context<S>) -> Result<Value<S>> {
+    self.skip_whitespace();
+
+    match self.peek() {
+      Some('{') => self.parse_nested(ctx, Self::parse_object),
+      Some('[') => self.parse_nested(ctx, Self::parse_array),
+      Some('"') => Ok(Value::String(self.parse_string()?)),
+      Some('t') => self.parse_literal("true", Value::Boolean(true)),
+      Some('f') => self.parse_literal("false", Value::Boolean(false)),
+      Some('n') => self.parse_literal("null", Value::default()),
+      Some('-' | '0'..='9') => self.parse_number(),
+      _ => Err(self.unexpected()),
+    }
+  }
+
+  fn parse_nested<S: Symbol>(
+    &mut self,
+    ctx: &mut dyn Context<S>,
+    parse: fn(&mut Self, &mut dyn Context<S>) -> Result<Value<S>>,
+  ) -> Result<Value<S>> {
+    if self.depth == MAX_DEPTH {
+      return Err(self.error(&format!("nesting deeper than {} levels", MAX_DEPTH)));
+    }
+
+    self.depth += 1;
+    let value = parse(self, ctx);
+    self.depth -= 1;
+    value
+  }
+
+  fn parse_literal<S: Symbol>(&mut self, word: &str, value: Value<S>) -> Result<Value<S>> {
+    for c in word.chars() {
+      self.expect(c)?;
+    }
+
+    Ok(value)
+  }
+
+  fn parse_object<S: Symbol>(&mut self, ctx: &mut dyn Context<S>) -> Result<Value<S>> {
+    self.expect('{')?;
+    self.skip_whitespace();
+
+    let mut map = HashMap::new();
+
+    if self.peek() == Some('}') {
+      self.next();
+      return Ok(Value::Map(Rc::new(map)));
+    }
+
+    loop {
+      self.skip_whitespace();
+
+      if self.peek() != Some('"') {
+        return Err(self.unexpected());
+      }
+
+      let (line, column) = (self.line, self.column);
+      let name = self.parse_string()?;
+      let key = match self.keywords {
+        true => Value::Keyword(ctx.intern(&name).into()),
+        false => Value::String(name.clone()),
+      };
+
+      self.skip_whitespace();
+      self.expect(':')?;
+      let val = self.parse_value(ctx)?;
+
+      if map.insert(key, val).is_some() {
+        return Err(RuntimeError::JsonError {
+          detail: format!("duplicate key {:?} at line {}, column {}", name, line, column),
+        });
+      }
+
+      self.skip_whitespace();
+
+      match self.peek() {
+        Some(',') => {
+          self.next();
+        },
+        Some('}') => {
+          self.next();
+          return Ok(Value::Map(Rc::new(map)));
+        },
+        _ => return Err(self.unexpected()),
+      }
+    }
+  }
+
+  fn parse_array<S: Symbol>(&mut self, ctx: &mut dyn Context<S>) -> Result<Value<S>> {
+    self.expect('[')?;
+    self.skip_whitespace();
+
+    let mut items = Vec::new();
+
+    if self.peek() == Some(']') {
+      self.next();
+      return Ok(Value::Vector(Rc::new(RefCell::new(items))));
+    }
+
+    loop {
+      items.push(self.parse_value(ctx)?);
+      self.skip_whitespace();
+
+      match self.peek() {
+        Some(',') => {
+          self.next();
+        },
+        Some(']') => {
+          self.next();
+          return Ok(Value::Vector(Rc::new(RefCell::new(items))));
+        },
+        _ => return Err(self.unexpected()),
+      }
+    }
+  }
+
+  fn parse_string(&mut self) -> Result<String> {
+    self.expect('"')?;
+
+    let mut s = String::new();
+
+    loop {
+      match self.peek() {
+        None => return Err(self.error("unterminated string")),
+        Some('"') => {
+          self.next();
+          return Ok(s);
+        },
+        Some('\\') => {
+          self.next();
+
+          let c = match self.peek() {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+              self.next();
+              s.push(self.parse_unicode_escape()?);
+              continue;
+            },
+            Some(c) => return Err(self.error(&format!("invalid escape \\{}", c))),
+            None => return Err(self.error("unterminated string")),
+          };
+
+          self.next();
+          s.push(c);
+        },
+        Some(c) if c < ' ' => {
+          return Err(self.error(&format!("unescaped control character {:?} in string", c)));
+        },
+        Some(c) => {
+          self.next();
+          s.push(c);
+        },
+      }
+    }
+  }
+
+  fn parse_hex4(&mut self) -> Result<u32> {
+    let mut code = 0;
+
+    for _ in 0..4 {
+      let digit = match self.peek().and_then(|c| c.to_digit(16)) {
+        Some(digit) => digit,
+        None => return Err(self.error("expected 4 hexadecimal digits in \\u escape")),
+      };
+
+      self.next();
+      code = code * 16 + digit;
+    }
+
+    Ok(code)
+  }
+
+  /// Decode the digits after `\u`, joining UTF-16 surrogate pairs.
+  fn parse_unicode_escape(&mut self) -> Result<char> {
+    let (line, column) = (self.line, self.column);
+    let lone_surrogate = |code: u32| RuntimeError::JsonError {
+      detail: format!(
+        "lone surrogate \\u{:04x} at line {}, column {}",
+        code, line, column.saturating_sub(2),
+      ),
+    };
+
+    let high = self.parse_hex4()?;
+
+    match high {
+      0xd800..=0xdbff => {
+        if self.peek() != Some('\\') {
+          return Err(lone_surrogate(high));
+        }
+
+        self.next();
+        self.expect('u')?;
+        let low = self.parse_hex4()?;
+
+        if !(0xdc00..=0xdfff).contains(&low) {
+          return Err(lone_surrogate(high));
+        }
+
+        let code = 0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00);
+        Ok(char::from_u32(code).expect("valid surrogate pair"))
+      },
+      0xdc00..=0xdfff => Err(lone_surrogate(high)),
+      code => Ok(char::from_u32(code).expect("valid scalar value")),
+    }
+  }
+
+  fn parse_digits(&mut self, text: &mut String) -> Result<()> {
+    if !matches!(self.peek(), Some('0'..='9')) {
+      return Err(self.unexpected());
+    }
+
+    while let Some(c @ '0'..='9') = self.peek() {
+      self.next();
+      text.push(c);
+    }
+
+    Ok(())
+  }
+
+  fn parse_number<S: Symbol>(&mut self) -> Result<Value<S>> {
+    let (line, column) = (self.line, self.column);
+    let out_of_range = |text: &str| RuntimeError::JsonError {
+      detail: format!("number {} out of range at line {}, column {}", text, line, column),
+    };
+
+    let mut text = String::new();
+    let mut integer = true;
+
+    if self.peek() == Some('-') {
+      self.next();
+      text.push('-');
+    }
+
+    if self.peek() == Some('0') {
+      self.next();
+      text.push('0');
+    }
+    else {
+      self.parse_digits(&mut text)?;
+    }
+
+    if self.peek() == Some('.') {
+      self.next();
+      text.push('.');
+      self.parse_digits(&mut text)?;
+      integer = false;
+    }
+
+    if let Some(e @ ('e' | 'E')) = self.peek() {
+      self.next();
+      text.push(e);
+
+      if let Some(sign @ ('+' | '-')) = self.peek() {
+        self.next();
+        text.push(sign);
+      }
+
+      self.parse_digits(&mut text)?;
+      integer = false;
+    }
+
+    if integer {
+      if let Ok(n) = text.parse() {
+        return Ok(Value::Integer(n));
+      }
+    }
+
+    let n: f64 = text.parse().expect("valid JSON number");
+
+    match n.is_finite() {
+      true => Ok(Value::Float(n)),
+      false => Err(out_of_range(&text)),
+    }
+  }
+}
+
+/// `(json-parse text [:keys :keyword])` decodes a JSON document. Object keys
+/// are strings unless `:keys` is `:keyword`.
+pub fn json_parse<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_at_least_args(1, args.len())?;
+
+  let text: String = (&args[0]).try_into()?;
+
+  let keyword = ctx.intern("keyword");
+  let keywords = match option(ctx, &args[1..], "keys")? {
+    None => false,
+    Some(Value::Keyword(key)) => key.as_symbol() == keyword,
+    Some(Value::String(key)) => key == "keyword",
+    Some(val) => return Err(RuntimeError::TypeError {
+      expected: "Keyword".to_string(),
+      got: format!("{:?}", val.as_type()),
+    }),
+  };
+
+  Parser::new(&text, keywords).parse_document(ctx)
+}
+
+fn stringify_string(s: &str, out: &mut String) {
+  out.push('"');
+
+  for c in s.chars() {
+    match c {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      '\r' => out.push_str("\\r"),
+      '\t' => out.push_str("\\t"),
+      '\u{8}' => out.push_str("\\b"),
+      '\u{c}' => out.push_str("\\f"),
+      c if c < ' ' || c == '\u{7f}' => out.push_str(&format!("\\u{:04x}", c as u32)),
+      c => out.push(c),
+    }
+  }
+
+  out.push('"');
+}
+
+struct Printer<'a, S: Symbol> {
+  ctx: &'a dyn Context<S>,
+  indent: Option<usize>,
+}
+
+impl<S: Symbol> Printer<'_, S> {
+  fn newline(&self, depth: usize, out: &mut String) {
+    if let Some(indent) = self.indent {
+      out.push('\n');
+      out.extend(std::iter::repeat_n(' ', indent * depth));
+    }
+  }
+
+  fn key(&self, key: &Value<S>) -> Result<String> {
+    match key {
+      Value::String(s) => Ok(s.clone()),
+      Value::Keyword(..) => Ok(self.ctx.display(key)[1..].to_string()),
+      _ => Err(RuntimeError::TypeError {
+        expected: "String".to_string(),
+        got: format!("{:?}", key.as_type()),
+      }),
+    }
+  }
+
+  fn array<'v>(
+    &self,
+    items: impl Iterator<Item = &'v Value<S>>,
+    depth: usize,
+    out: &mut String,
+  ) -> Result<()> where S: 'v {
+    out.push('[');
+    let mut empty = true;
+
+    for (index, item) in items.enumerate() {
+      if index > 0 {
+        out.push(',');
+      }
+
+      self.newline(depth + 1, out);
+      self.value(item, depth + 1, out)?;
+      empty = false;
+    }
+
+    if !empty {
+      self.newline(depth, out);
+    }
+
+    out.push(']');
+    Ok(())
+  }
+
+  fn object<'v>(
+    &self,
+    entries: impl Iterator<Item = (&'v Value<S>, &'v Value<S>)>,
+    depth: usize,
+    out: &mut String,
+  ) -> Result<()> where S: 'v {
+    let mut entries = entries
+      .map(|(key, val)| Ok((self.key(key)?, val)))
+      .collect::<Result<Vec<_>>>()?;
+
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    if let Some(pair) = entries.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+      return Err(RuntimeError::JsonError {
+        detail: format!("duplicate key {:?} in object", pair[0].0),
+      });
+    }
+
+    out.push('{');
+
+    for (index, (key, val)) in entries.iter().enumerate() {
+      if index > 0 {
+        out.push(',');
+      }
+
+      self.newline(depth + 1, out);
+      stringify_string(key, out);
+      out.push_str(if self.indent.is_some() { ": " } else { ":" });
+      self.value(val, depth + 1, out)?;
+    }
+
+    if !entries.is_empty() {
+      self.newline(depth, out);
+    }
+
+    out.push('}');
+    Ok(())
+  }
+
+  fn value(&self, value: &Value<S>, depth: usize, out: &mut String) -> Result<()> {
+    match value {
+      Value::Boolean(val) => out.push_str(&val.to_string()),
+      Value::Integer(val) => out.push_str(&val.to_string()),
+      Value::Float(val) if val.is_finite() => out.push_str(&format!("{:?}", val)),
+      Value::Float(val) => {
+        return Err(RuntimeError::JsonError {
+          detail: format!("cannot encode {} as a number", val),
+        });
+      },
+      Value::Char(val) => stringify_string(&val.to_string(), out),
+      Value::String(val) => stringify_string(val, out),
+      Value::Keyword(..) => stringify_string(&self.ctx.display(value)[1..], out),
+      Value::List(list) if list.empty() => out.push_str("null"),
+      Value::List(list) => {
+        let items: Vec<Value<S>> = list.into_iter().collect();
+        self.array(items.iter(), depth, out)?;
+      },
+      Value::Vector(items) => self.array(items.borrow().iter(), depth, out)?,
+      Value::PersistentVector(items) => self.array(items.iter(), depth, out)?,
+      Value::Map(map) => self.object(map.iter(), depth, out)?,
+      Value::PersistentMap(map) => self.object(map.iter(), depth, out)?,
+      Value::Symbol(..) | Value::Function(..) | Value::Port(..) => {
+        return Err(RuntimeError::TypeError {
+          expected: "Json".to_string(),
+          got: format!("{:?}", value.as_type()),
+        });
+      },
+    }
+
+    Ok(())
+  }
+}
+
+/// `(json-stringify value [:pretty true-or-indent])` encodes a value as a
+/// JSON document, compact by default. `:pretty true` indents by 2 spaces.
+pub fn json_stringify<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_at_least_args(1, args.len())?;
+
+  let indent = match option(ctx, &args[1..], "pretty")? {
+    None => None,
+    Some(Value::Integer(n)) => Some(n.max(0) as usize),
+    Some(val) if val.is_truthy() => Some(2),
+    Some(_) => None,
+  };
+
+  let mut out = String::new();
+  Printer { ctx, indent }.value(&args[0], 0, &mut out)?;
+  Ok(Value::String(out))
+}
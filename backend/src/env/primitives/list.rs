@@ -0,0 +1,229 @@
+use std::{rc::Rc, cell::RefCell};
+use lispers_common::Symbol;
+
+use crate::prelude::*;
+use crate::data::{Value, List, Context};
+use crate::env::Env;
+
+use crate::utils::assert_exactly_args;
+
+fn list_index<S: Symbol>(list: &List<S>, index: &Value<S>) -> Result<usize> {
+  let index: i64 = index.try_into()?;
+
+  if index < 0 {
+    let length = list.into_iter().count();
+    Err(RuntimeError::IndexOutOfBounds { index, length })
+  }
+  else {
+    Ok(index as usize)
+  }
+}
+
+pub fn list<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  Ok(Value::List(args.into_iter().collect()))
+}
+
+pub fn cons<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let list: List<S> = (&args[1]).try_into()?;
+  Ok(Value::List(list.cons(args[0].clone())))
+}
+
+pub fn car<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let list: List<S> = (&args[0]).try_into()?;
+  list.car()
+}
+
+pub fn cdr<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let list: List<S> = (&args[0]).try_into()?;
+  Ok(Value::List(list.cdr()))
+}
+
+pub fn is_null<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let result = matches!(&args[0], Value::List(list) if list.empty());
+  Ok(Value::Boolean(result))
+}
+
+pub fn length<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let list: List<S> = (&args[0]).try_into()?;
+  Ok(Value::Integer(list.into_iter().count() as i64))
+}
+
+pub fn append<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  let mut items = Vec::new();
+
+  for arg in args {
+    let list: List<S> = arg.try_into()?;
+    items.extend(&list);
+  }
+
+  Ok(Value::List(items.into_iter().collect()))
+}
+
+pub fn reverse<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let list: List<S> = (&args[0]).try_into()?;
+  let reversed = list
+    .into_iter()
+    .fold(List::NIL, |reversed, item| reversed.cons(item));
+
+  Ok(Value::List(reversed))
+}
+
+pub fn nth<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let list: List<S> = (&args[0]).try_into()?;
+  let index = list_index(&list, &args[1])?;
+
+  list.into_iter().nth(index).ok_or_else(|| RuntimeError::IndexOutOfBounds {
+    index: index as i64,
+    length: list.into_iter().count(),
+  })
+}
+
+pub fn last<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let list: List<S> = (&args[0]).try_into()?;
+  list.into_iter().last().ok_or_else(|| RuntimeError::NilValue {
+    detail: "last called on empty list".to_string(),
+  })
+}
+
+pub fn take<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let list: List<S> = (&args[0]).try_into()?;
+  let count = list_index(&list, &args[1])?;
+
+  Ok(Value::List(list.into_iter().take(count).collect()))
+}
+
+pub fn drop<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let mut list: List<S> = (&args[0]).try_into()?;
+  let count = list_index(&list, &args[1])?;
+
+  for _ in 0..count {
+    if list.empty() {
+      break;
+    }
+
+    list = list.cdr();
+  }
+
+  Ok(Value::List(list))
+}
+
+pub fn member<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let mut list: List<S> = (&args[1]).try_into()?;
+
+  while !list.empty() {
+    if list.car()? == args[0] {
+      return Ok(Value::List(list));
+    }
+
+    list = list.cdr();
+  }
+
+  Ok(Value::Boolean(false))
+}
+
+/// Association list lookup: `(assoc key alist)` returns the first pair whose
+/// `car` equals `key`.
+pub fn assoc<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let alist: List<S> = (&args[1]).try_into()?;
+
+  for pair in alist.into_iter() {
+    let entry: List<S> = (&pair).try_into()?;
+
+    if !entry.empty() && entry.car()? == args[0] {
+      return Ok(pair);
+    }
+  }
+
+  Ok(Value::Boolean(false))
+}
+
+pub fn list_copy<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let list: List<S> = (&args[0]).try_into()?;
+  Ok(Value::List(list.into_iter().collect()))
+}
@@ -0,0 +1,218 @@
+use std::{
+  collections::HashMap,
+  rc::Rc,
+  cell::RefCell,
+};
+use lispers_common::Symbol;
+
+use crate::prelude::*;
+use crate::data::{Value, List, PersistentMap, Context};
+use crate::env::Env;
+
+use crate::utils::{assert_exactly_args, assert_at_least_args, assert_args_between, assert_pairs};
+
+type Map<S> = Rc<HashMap<Value<S>, Value<S>>>;
+
+use super::{vector::assoc_index, list};
+
+fn insert_pairs<S: Symbol>(
+  map: &mut HashMap<Value<S>, Value<S>>,
+  pairs: &[Value<S>],
+) -> Result<()> {
+  for pair in pairs.chunks(2) {
+    let key = &pair[0];
+    let val = &pair[1];
+
+    key.assert_hashable()?;
+    map.insert(key.clone(), val.clone());
+  }
+
+  Ok(())
+}
+
+pub fn hash_map<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_pairs(args.len())?;
+
+  let mut map = HashMap::with_capacity(args.len() / 2);
+  insert_pairs(&mut map, &args)?;
+  Ok(Value::Map(Rc::new(map)))
+}
+
+pub fn persistent_hash_map<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_pairs(args.len())?;
+
+  let mut map = PersistentMap::new();
+
+  for pair in args.chunks(2) {
+    pair[0].assert_hashable()?;
+    map = map.insert(pair[0].clone(), pair[1].clone());
+  }
+
+  Ok(Value::PersistentMap(map))
+}
+
+pub fn get<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(2, Some(3), args.len())?;
+
+  let default = args.get(2).cloned().unwrap_or_default();
+
+  let val = match &args[0] {
+    Value::PersistentMap(map) => map.get(&args[1]).cloned(),
+    val => {
+      let map: Map<S> = val.try_into()?;
+      map.get(&args[1]).cloned()
+    },
+  };
+
+  Ok(val.unwrap_or(default))
+}
+
+/// `(assoc coll key val ...)` updates maps and persistent vectors, any other
+/// first argument makes it an association list lookup, `(assoc key alist)`.
+pub fn assoc<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  if !matches!(
+    args.first(),
+    Some(Value::Map(..) | Value::PersistentMap(..) | Value::PersistentVector(..)),
+  ) {
+    return list::assoc(ctx, env, args);
+  }
+
+  assert_at_least_args(3, args.len())?;
+  assert_pairs(args.len() - 1)?;
+
+  match &args[0] {
+    Value::PersistentVector(vector) => assoc_index(vector, &args[1..]),
+    Value::PersistentMap(map) => {
+      let mut map = map.clone();
+
+      for pair in args[1..].chunks(2) {
+        pair[0].assert_hashable()?;
+        map = map.insert(pair[0].clone(), pair[1].clone());
+      }
+
+      Ok(Value::PersistentMap(map))
+    },
+    val => {
+      let map: Map<S> = val.try_into()?;
+      let mut map = map.as_ref().clone();
+      insert_pairs(&mut map, &args[1..])?;
+
+      Ok(Value::Map(Rc::new(map)))
+    },
+  }
+}
+
+pub fn dissoc<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_at_least_args(1, args.len())?;
+
+  if let Value::PersistentMap(map) = &args[0] {
+    let map = args[1..].iter().fold(map.clone(), |map, key| map.remove(key));
+    return Ok(Value::PersistentMap(map));
+  }
+
+  let map: Map<S> = (&args[0]).try_into()?;
+  let mut map = map.as_ref().clone();
+
+  for key in args[1..].iter() {
+    map.remove(key);
+  }
+
+  Ok(Value::Map(Rc::new(map)))
+}
+
+pub fn contains<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let found = match &args[0] {
+    Value::PersistentMap(map) => map.contains_key(&args[1]),
+    val => {
+      let map: Map<S> = val.try_into()?;
+      map.contains_key(&args[1])
+    },
+  };
+
+  Ok(Value::Boolean(found))
+}
+
+pub fn keys<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let list: List<S> = match &args[0] {
+    Value::PersistentMap(map) => map.keys().cloned().collect(),
+    val => {
+      let map: Map<S> = val.try_into()?;
+      map.keys().cloned().collect()
+    },
+  };
+
+  Ok(Value::List(list))
+}
+
+pub fn vals<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let list: List<S> = match &args[0] {
+    Value::PersistentMap(map) => map.values().cloned().collect(),
+    val => {
+      let map: Map<S> = val.try_into()?;
+      map.values().cloned().collect()
+    },
+  };
+
+  Ok(Value::List(list))
+}
+
+pub fn map_to_list<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let pair = |(key, val): (&Value<S>, &Value<S>)| {
+    let pair: List<S> = [key.clone(), val.clone()].into_iter().collect();
+    Value::List(pair)
+  };
+
+  let list: List<S> = match &args[0] {
+    Value::PersistentMap(map) => map.iter().map(pair).collect(),
+    val => {
+      let map: Map<S> = val.try_into()?;
+      map.iter().map(pair).collect()
+    },
+  };
+
+  Ok(Value::List(list))
+}
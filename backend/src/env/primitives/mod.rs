@@ -1,3 +1,13 @@
 pub mod arithmetic;
 pub mod comparison;
+pub mod list;
+pub mod higher_order;
+pub mod types;
 pub mod proc;
+pub mod vector;
+pub mod map;
+pub mod string;
+pub mod io;
+pub mod format;
+pub mod json;
+pub mod time;
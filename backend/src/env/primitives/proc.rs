@@ -2,12 +2,13 @@ use std::{rc::Rc, cell::RefCell};
 use lispers_common::Symbol;
 
 use crate::prelude::*;
-use crate::data::Value;
+use crate::data::{Value, Context};
 use crate::env::Env;
 
 use crate::utils::assert_exactly_args;
 
 pub fn exit<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
   _env: Rc<RefCell<Env<S>>>,
   args: Vec<Value<S>>,
 ) -> Result<Value<S>> {
@@ -0,0 +1,358 @@
+use std::{rc::Rc, cell::RefCell};
+use lispers_common::Symbol;
+
+use crate::prelude::*;
+use crate::data::{Value, Type, List, Context};
+use crate::env::Env;
+
+use crate::utils::{assert_exactly_args, assert_args_between};
+
+// All indices are expressed in Unicode scalar values (chars), not bytes.
+
+fn char_index<S: Symbol>(length: usize, index: &Value<S>) -> Result<usize> {
+  let index: i64 = index.try_into()?;
+
+  if index < 0 || index as usize > length {
+    Err(RuntimeError::IndexOutOfBounds { index, length })
+  }
+  else {
+    Ok(index as usize)
+  }
+}
+
+fn pattern<S: Symbol>(value: &Value<S>) -> Result<String> {
+  match value {
+    Value::Char(c) => Ok(c.to_string()),
+    Value::String(s) => Ok(s.clone()),
+    _ => Err(Type::error(value.as_type(), Type::String)),
+  }
+}
+
+fn format_radix(mut n: i64, radix: u32) -> String {
+  if n == 0 {
+    return "0".to_string();
+  }
+
+  let negative = n < 0;
+  let mut digits = Vec::new();
+
+  while n != 0 {
+    let digit = (n % radix as i64).unsigned_abs() as u32;
+    digits.push(std::char::from_digit(digit, radix).unwrap_or('?'));
+    n /= radix as i64;
+  }
+
+  if negative {
+    digits.push('-');
+  }
+
+  digits.iter().rev().collect()
+}
+
+/// Whether `s` is written like a float literal, Rust's parser also accepts
+/// `inf` and `nan` which the reader does not.
+fn is_decimal(s: &str) -> bool {
+  s.chars().any(|c| c.is_ascii_digit())
+    && s.chars().all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'))
+}
+
+fn radix_arg<S: Symbol>(args: &[Value<S>], index: usize) -> Result<u32> {
+  match args.get(index) {
+    None => Ok(10),
+    Some(val) => {
+      let radix: i64 = val.try_into()?;
+
+      if (2..=36).contains(&radix) {
+        Ok(radix as u32)
+      }
+      else {
+        Err(RuntimeError::TypeError {
+          expected: "Radix".to_string(),
+          got: radix.to_string(),
+        })
+      }
+    },
+  }
+}
+
+pub fn string_length<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  Ok(Value::Integer(s.chars().count() as i64))
+}
+
+pub fn substring<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(2, Some(3), args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  let length = s.chars().count();
+
+  let start = char_index(length, &args[1])?;
+  let end = match args.get(2) {
+    Some(end) => char_index(length, end)?,
+    None => length,
+  };
+
+  if end < start {
+    return Err(RuntimeError::IndexOutOfBounds { index: end as i64, length });
+  }
+
+  let result: String = s.chars().skip(start).take(end - start).collect();
+  Ok(Value::String(result))
+}
+
+pub fn string_append<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  let mut result = String::new();
+
+  for arg in args {
+    let s: String = arg.try_into()?;
+    result.push_str(&s);
+  }
+
+  Ok(Value::String(result))
+}
+
+pub fn string_split<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(1, Some(2), args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+
+  let parts: List<S> = match args.get(1) {
+    None => {
+      s.split_whitespace()
+        .map(|part| Value::String(part.to_string()))
+        .collect()
+    },
+    Some(sep) => {
+      let sep = pattern(sep)?;
+
+      if sep.is_empty() {
+        s.chars()
+          .map(|c| Value::String(c.to_string()))
+          .collect()
+      }
+      else {
+        s.split(sep.as_str())
+          .map(|part| Value::String(part.to_string()))
+          .collect()
+      }
+    },
+  };
+
+  Ok(Value::List(parts))
+}
+
+pub fn string_join<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(1, Some(2), args.len())?;
+
+  let parts: List<S> = (&args[0]).try_into()?;
+  let sep = match args.get(1) {
+    Some(sep) => pattern(sep)?,
+    None => String::new(),
+  };
+
+  let mut strings = Vec::new();
+
+  for part in parts.into_iter() {
+    let part: String = part.try_into()?;
+    strings.push(part);
+  }
+
+  Ok(Value::String(strings.join(&sep)))
+}
+
+pub fn string_upcase<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  Ok(Value::String(s.to_uppercase()))
+}
+
+pub fn string_downcase<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  Ok(Value::String(s.to_lowercase()))
+}
+
+pub fn string_index<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  let needle = pattern(&args[1])?;
+
+  match s.find(needle.as_str()) {
+    Some(offset) => Ok(Value::Integer(s[..offset].chars().count() as i64)),
+    None => Ok(Value::Boolean(false)),
+  }
+}
+
+pub fn string_to_list<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  let chars: List<S> = s.chars().map(Value::Char).collect();
+  Ok(Value::List(chars))
+}
+
+pub fn list_to_string<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let chars: List<S> = (&args[0]).try_into()?;
+  let mut result = String::new();
+
+  for c in chars.into_iter() {
+    let c: char = c.try_into()?;
+    result.push(c);
+  }
+
+  Ok(Value::String(result))
+}
+
+pub fn string_to_number<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(1, Some(2), args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  let s = s.trim();
+  let radix = radix_arg(&args, 1)?;
+
+  if let Ok(n) = i64::from_str_radix(s, radix) {
+    return Ok(Value::Integer(n));
+  }
+
+  if radix == 10 && is_decimal(s) {
+    match s.parse::<f64>() {
+      Ok(n) if n.is_finite() => return Ok(Value::Float(n)),
+      _ => {},
+    }
+  }
+
+  Ok(Value::Boolean(false))
+}
+
+pub fn number_to_string<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_args_between(1, Some(2), args.len())?;
+
+  let radix = radix_arg(&args, 1)?;
+
+  match &args[0] {
+    Value::Integer(n) => Ok(Value::String(format_radix(*n, radix))),
+    Value::Float(n) if radix == 10 => Ok(Value::String(n.to_string())),
+    val => Err(Type::error(val.as_type(), Type::Integer)),
+  }
+}
+
+pub fn string_trim<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  Ok(Value::String(s.trim().to_string()))
+}
+
+pub fn string_contains<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  let needle = pattern(&args[1])?;
+  Ok(Value::Boolean(s.contains(needle.as_str())))
+}
+
+pub fn string_replace<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(3, args.len())?;
+
+  let s: String = (&args[0]).try_into()?;
+  let from = pattern(&args[1])?;
+  let to = pattern(&args[2])?;
+  Ok(Value::String(s.replace(from.as_str(), to.as_str())))
+}
+
+pub fn char_to_integer<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let c: char = (&args[0]).try_into()?;
+  Ok(Value::Integer(c as i64))
+}
+
+pub fn integer_to_char<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let code: i64 = (&args[0]).try_into()?;
+
+  u32::try_from(code)
+    .ok()
+    .and_then(char::from_u32)
+    .map(Value::Char)
+    .ok_or_else(|| RuntimeError::TypeError {
+      expected: "UnicodeScalarValue".to_string(),
+      got: code.to_string(),
+    })
+}
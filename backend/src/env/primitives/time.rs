@@ -0,0 +1,23 @@
+use std::{rc::Rc, cell::RefCell, time::{SystemTime, UNIX_EPOCH}};
+use lispers_common::Symbol;
+
+use crate::prelude::*;
+use crate::data::{Value, Context};
+use crate::env::Env;
+
+use crate::utils::assert_exactly_args;
+
+/// `(current-time)` returns the seconds elapsed since the Unix epoch.
+pub fn current_time<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(0, args.len())?;
+
+  let elapsed = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap_or_default();
+
+  Ok(Value::Float(elapsed.as_secs_f64()))
+}
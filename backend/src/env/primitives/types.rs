@@ -0,0 +1,187 @@
+use std::{rc::Rc, cell::RefCell};
+use lispers_common::Symbol;
+
+use crate::prelude::*;
+use crate::data::{Value, Function, Context};
+use crate::env::Env;
+
+use crate::utils::assert_exactly_args;
+
+pub fn type_of<S: Symbol>(
+  ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let name = ctx.intern(args[0].as_type().name());
+  Ok(Value::Keyword(name.into()))
+}
+
+pub fn is_boolean<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::Boolean(..))))
+}
+
+pub fn is_integer<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::Integer(..))))
+}
+
+pub fn is_float<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::Float(..))))
+}
+
+pub fn is_number<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::Integer(..) | Value::Float(..))))
+}
+
+pub fn is_char<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::Char(..))))
+}
+
+pub fn is_string<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::String(..))))
+}
+
+pub fn is_symbol<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::Symbol(..))))
+}
+
+pub fn is_keyword<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::Keyword(..))))
+}
+
+pub fn is_list<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::List(..))))
+}
+
+pub fn is_pair<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::List(list) if !list.empty())))
+}
+
+pub fn is_vector<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::Vector(..) | Value::PersistentVector(..))))
+}
+
+pub fn is_map<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::Map(..) | Value::PersistentMap(..))))
+}
+
+pub fn is_procedure<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::Function(..))))
+}
+
+pub fn is_port<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+  Ok(Value::Boolean(matches!(&args[0], Value::Port(..))))
+}
+
+/// Returns `(min max)` for lambdas, `max` being `false` when they take a rest
+/// parameter. Native functions validate their own arguments, so their arity
+/// is unknown and reported as `false`.
+pub fn arity<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let func: Function<S> = (&args[0]).try_into()?;
+
+  match func {
+    Function::NativeFn { .. } => Ok(Value::Boolean(false)),
+    Function::Lambda(lambda) => {
+      let (min, max) = lambda.params.arity();
+      let min = Value::Integer(min as i64);
+      let max = max.map(|max| Value::Integer(max as i64)).unwrap_or(Value::Boolean(false));
+      Ok(Value::List([min, max].into_iter().collect()))
+    },
+  }
+}
+
+/// Returns the symbol a function was defined as, or `false` for anonymous
+/// lambdas.
+pub fn procedure_name<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let func: Function<S> = (&args[0]).try_into()?;
+
+  let name = match func {
+    Function::NativeFn { name, .. } => Some(name),
+    Function::Lambda(lambda) => lambda.name,
+  };
+
+  Ok(name.map(|name| Value::Symbol(name.into())).unwrap_or(Value::Boolean(false)))
+}
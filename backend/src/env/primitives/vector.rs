@@ -0,0 +1,197 @@
+use std::{rc::Rc, cell::RefCell};
+use lispers_common::Symbol;
+
+use crate::prelude::*;
+use crate::data::{Value, List, PersistentVector, Context};
+use crate::env::Env;
+
+use crate::utils::{assert_exactly_args, assert_at_least_args};
+
+type Vector<S> = Rc<RefCell<Vec<Value<S>>>>;
+
+fn vector_index<S: Symbol>(length: usize, index: &Value<S>) -> Result<usize> {
+  let index: i64 = index.try_into()?;
+
+  if index < 0 || index as usize >= length {
+    Err(RuntimeError::IndexOutOfBounds { index, length })
+  }
+  else {
+    Ok(index as usize)
+  }
+}
+
+pub fn vector<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  Ok(Value::Vector(Rc::new(RefCell::new(args))))
+}
+
+pub fn persistent_vector<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  Ok(Value::PersistentVector(args.into_iter().collect()))
+}
+
+pub fn vector_ref<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  match &args[0] {
+    Value::PersistentVector(vector) => {
+      let index = vector_index(vector.len(), &args[1])?;
+      Ok(vector.get(index).cloned().unwrap_or_default())
+    },
+    val => {
+      let vector: Vector<S> = val.try_into()?;
+      let index = vector_index(vector.borrow().len(), &args[1])?;
+
+      let item = vector.borrow()[index].clone();
+      Ok(item)
+    },
+  }
+}
+
+pub fn vector_set<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(3, args.len())?;
+
+  let vector: Vector<S> = (&args[0]).try_into()?;
+  let index = vector_index(vector.borrow().len(), &args[1])?;
+
+  vector.borrow_mut()[index] = args[2].clone();
+  Ok(args[2].clone())
+}
+
+pub fn vector_length<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let length = match &args[0] {
+    Value::PersistentVector(vector) => vector.len(),
+    val => {
+      let vector: Vector<S> = val.try_into()?;
+      let length = vector.borrow().len();
+      length
+    },
+  };
+
+  Ok(Value::Integer(length as i64))
+}
+
+pub fn vector_push<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(2, args.len())?;
+
+  let vector: Vector<S> = (&args[0]).try_into()?;
+  vector.borrow_mut().push(args[1].clone());
+  Ok(Value::Vector(vector))
+}
+
+pub fn conj<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_at_least_args(2, args.len())?;
+
+  let vector: PersistentVector<Value<S>> = (&args[0]).try_into()?;
+  let vector = args[1..]
+    .iter()
+    .fold(vector, |vector, item| vector.push(item.clone()));
+
+  Ok(Value::PersistentVector(vector))
+}
+
+pub fn pop<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let vector: PersistentVector<Value<S>> = (&args[0]).try_into()?;
+  let vector = vector.pop().ok_or_else(|| RuntimeError::NilValue {
+    detail: "pop called on empty vector".to_string(),
+  })?;
+
+  Ok(Value::PersistentVector(vector))
+}
+
+pub fn vector_to_list<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let list: List<S> = match &args[0] {
+    Value::PersistentVector(vector) => vector.iter().cloned().collect(),
+    val => {
+      let vector: Vector<S> = val.try_into()?;
+      let list = vector.borrow().iter().cloned().collect();
+      list
+    },
+  };
+
+  Ok(Value::List(list))
+}
+
+pub fn list_to_vector<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let list: List<S> = (&args[0]).try_into()?;
+  let items: Vec<Value<S>> = list.into_iter().collect();
+  Ok(Value::Vector(Rc::new(RefCell::new(items))))
+}
+
+pub fn list_to_persistent_vector<S: Symbol>(
+  _ctx: &mut dyn Context<S>,
+  _env: Rc<RefCell<Env<S>>>,
+  args: Vec<Value<S>>,
+) -> Result<Value<S>> {
+  assert_exactly_args(1, args.len())?;
+
+  let list: List<S> = (&args[0]).try_into()?;
+  Ok(Value::PersistentVector(list.into_iter().collect()))
+}
+
+pub(super) fn assoc_index<S: Symbol>(
+  vector: &PersistentVector<Value<S>>,
+  pairs: &[Value<S>],
+) -> Result<Value<S>> {
+  let mut vector = vector.clone();
+
+  for pair in pairs.chunks(2) {
+    let index: i64 = (&pair[0]).try_into()?;
+
+    vector = if index >= 0 && index as usize == vector.len() {
+      vector.push(pair[1].clone())
+    }
+    else {
+      let index = vector_index(vector.len(), &pair[0])?;
+      vector.set(index, pair[1].clone()).unwrap_or(vector)
+    };
+  }
+
+  Ok(Value::PersistentVector(vector))
+}
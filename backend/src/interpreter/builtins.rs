@@ -2,11 +2,11 @@ use std::{rc::Rc, cell::RefCell};
 use lispers_common::{Backend, Symbol};
 
 use crate::prelude::*;
-use crate::data::{Value, Sym, List, Function, Lambda};
+use crate::data::{Value, Type, Sym, List, Function, Lambda, Params, Context};
 use crate::env::Env;
 use super::Interpreter;
 
-use crate::utils::{assert_exactly_args, assert_at_least_args};
+use crate::utils::{assert_exactly_args, assert_at_least_args, assert_args_between};
 
 impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
   pub fn builtin_println(&mut self, env: Rc<RefCell<Env<S>>>, args: Vec<Value<S>>) -> Result<Value<S>> {
@@ -25,13 +25,14 @@ impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
       .collect::<Vec<String>>()
       .join(" ");
 
-    println!("{}", output);
+    self.output.write_str(&format!("{}\n", output))?;
     Ok(Value::default())
   }
 
   pub fn builtin_quote(&mut self, args: Vec<Value<S>>) -> Result<Value<S>> {
     assert_exactly_args(1, args.len())?;
     let arg = &args[0];
+    arg.assert_hashable_keys()?;
     Ok(arg.clone())
   }
 
@@ -44,10 +45,25 @@ impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
     let var = &args[0];
     let val = &args[1];
 
-    let sym: Sym<S> = var.try_into()?;
-    let sym = sym.as_symbol();
+    let sym = match var {
+      Value::Symbol(sym) => sym.as_symbol(),
+      pattern => {
+        let val = self.eval_expression(env.clone(), val.clone())?;
+        self.bind_pattern(&env, pattern, val.clone())?;
+        return Ok(val);
+      },
+    };
+
+    let val = match self.eval_expression(env.clone(), val.clone())? {
+      Value::Function(Function::Lambda(lambda)) if lambda.name.is_none() => {
+        Value::Function(Function::Lambda(Rc::new(Lambda {
+          name: Some(sym),
+          ..lambda.as_ref().clone()
+        })))
+      },
+      val => val,
+    };
 
-    let val = self.eval_expression(env.clone(), val.clone())?;
     env.borrow_mut().define(sym, val.clone());
     Ok(val)
   }
@@ -73,85 +89,647 @@ impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
     Ok(val)
   }
 
+  /// `(if test then)` or `(if test then else)`, a missing else yields nil.
   pub fn builtin_controlflow_if(
     &mut self,
     env: Rc<RefCell<Env<S>>>,
     args: Vec<Value<S>>,
   ) -> Result<Value<S>> {
-    assert_exactly_args(3, args.len())?;
+    assert_args_between(2, Some(3), args.len())?;
 
     let test = &args[0];
     let true_branch = &args[1];
-    let false_branch = &args[2];
+    let false_branch = args.get(2);
 
     let test_result = self.eval_expression(env.clone(), test.clone())?;
-    let test_result: bool = test_result.try_into()?;
 
-    let branch = if test_result {
-      true_branch
+    if test_result.is_truthy() {
+      self.eval_expression(env.clone(), true_branch.clone())
+    }
+    else {
+      match false_branch {
+        Some(branch) => self.eval_expression(env.clone(), branch.clone()),
+        None => Ok(Value::default()),
+      }
+    }
+  }
+
+  /// Yields the first falsy value, or the last value when all are truthy.
+  pub fn builtin_controlflow_and(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    let mut result = Value::Boolean(true);
+
+    for arg in args {
+      result = self.eval_expression(env.clone(), arg)?;
+
+      if !result.is_truthy() {
+        break;
+      }
+    }
+
+    Ok(result)
+  }
+
+  /// Yields the first truthy value, or the last value when all are falsy.
+  pub fn builtin_controlflow_or(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    let mut result = Value::Boolean(false);
+
+    for arg in args {
+      result = self.eval_expression(env.clone(), arg)?;
+
+      if result.is_truthy() {
+        break;
+      }
+    }
+
+    Ok(result)
+  }
+
+  /// `(cond (test body ...) (test => func) (else body ...))`. A clause
+  /// without body yields the value of its test, no matching clause yields nil.
+  pub fn builtin_controlflow_cond(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    for clause in args {
+      let clause: List<S> = clause.try_into()?;
+      let clause: Vec<Value<S>> = clause.into_iter().collect();
+      assert_at_least_args(1, clause.len())?;
+
+      let test = if self.is_symbol_named(&clause[0], "else") {
+        Value::Boolean(true)
+      }
+      else {
+        self.eval_expression(env.clone(), clause[0].clone())?
+      };
+
+      if !test.is_truthy() {
+        continue;
+      }
+
+      if clause.len() > 1 && self.is_symbol_named(&clause[1], "=>") {
+        assert_exactly_args(3, clause.len())?;
+        let func = self.eval_expression(env.clone(), clause[2].clone())?;
+        return self.apply(env.clone(), func, vec![test]);
+      }
+
+      if clause.len() == 1 {
+        return Ok(test);
+      }
+
+      return self.eval_body(env.clone(), &clause[1..]);
+    }
+
+    Ok(Value::default())
+  }
+
+  /// `(case key ((datum ...) body ...) (else body ...))`, the data are not
+  /// evaluated and are compared to the key with `eqv?`.
+  pub fn builtin_controlflow_case(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(1, args.len())?;
+
+    let key = self.eval_expression(env.clone(), args[0].clone())?;
+
+    for clause in &args[1..] {
+      let clause: List<S> = clause.try_into()?;
+      let clause: Vec<Value<S>> = clause.into_iter().collect();
+      assert_at_least_args(1, clause.len())?;
+
+      let matched = if self.is_symbol_named(&clause[0], "else") {
+        true
+      }
+      else {
+        let data: List<S> = (&clause[0]).try_into()?;
+        data.into_iter().any(|datum| datum.is_eqv(&key))
+      };
+
+      if matched {
+        return self.eval_body(env.clone(), &clause[1..]);
+      }
+    }
+
+    Ok(Value::default())
+  }
+
+  /// `(match expr (pattern body ...) (pattern when guard body ...) ...)`
+  /// evaluates the body of the first clause whose pattern matches (see the
+  /// `pattern` module) and whose guard, if any, is truthy. The guard and body
+  /// see the bindings of the pattern.
+  pub fn builtin_match(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(1, args.len())?;
+
+    let value = self.eval_expression(env.clone(), args[0].clone())?;
+
+    for clause in &args[1..] {
+      let clause: List<S> = clause.try_into()?;
+      let clause: Vec<Value<S>> = clause.into_iter().collect();
+      assert_at_least_args(1, clause.len())?;
+
+      let scope = Rc::new(RefCell::new(Env::extend(env.clone())));
+
+      if !self.match_pattern(&scope, &clause[0], &value)? {
+        continue;
+      }
+
+      let body = match clause.get(1) {
+        Some(keyword) if self.is_symbol_named(keyword, "when") => {
+          assert_at_least_args(3, clause.len())?;
+          let guard = self.eval_expression(scope.clone(), clause[2].clone())?;
+
+          if !guard.is_truthy() {
+            continue;
+          }
+
+          &clause[3..]
+        },
+        _ => &clause[1..],
+      };
+
+      return self.eval_body(scope, body);
+    }
+
+    Err(RuntimeError::MatchError { value: self.format_value(&value) })
+  }
+
+  /// `(when test body ...)` and `(unless test body ...)`, yielding nil when
+  /// the body is skipped.
+  pub fn builtin_controlflow_when(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+    expected: bool,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(1, args.len())?;
+
+    let test = self.eval_expression(env.clone(), args[0].clone())?;
+
+    if test.is_truthy() == expected {
+      self.eval_body(env.clone(), &args[1..])
     }
     else {
-      false_branch
+      Ok(Value::default())
+    }
+  }
+
+  /// Run one iteration of a loop body, returning `false` once a `break`
+  /// ends the loop. `break` and `continue` unwind to the innermost loop as
+  /// `RuntimeError::Break` and `RuntimeError::Continue`, but never out of the
+  /// function they appear in.
+  fn eval_loop_body(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    body: &[Value<S>],
+  ) -> Result<bool> {
+    match self.eval_body(env, body) {
+      Ok(_) | Err(RuntimeError::Continue) => Ok(true),
+      Err(RuntimeError::Break) => Ok(false),
+      Err(err) => Err(err),
+    }
+  }
+
+  fn loop_result(&mut self) -> Value<S> {
+    self.break_value.take().unwrap_or_default()
+  }
+
+  /// `(while test body ...)` yields nil, or the value given to `break`.
+  pub fn builtin_loop_while(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(1, args.len())?;
+
+    while self.eval_expression(env.clone(), args[0].clone())?.is_truthy() {
+      let scope = Rc::new(RefCell::new(Env::extend(env.clone())));
+
+      if !self.eval_loop_body(scope, &args[1..])? {
+        return Ok(self.loop_result());
+      }
+    }
+
+    Ok(Value::default())
+  }
+
+  /// Parse the `(name expr [result])` header of `dotimes` and `dolist`.
+  fn parse_loop_header(&self, header: &Value<S>) -> Result<(S, Value<S>, Option<Value<S>>)> {
+    let header: List<S> = header.try_into()?;
+    let header: Vec<Value<S>> = header.into_iter().collect();
+    assert_args_between(2, Some(3), header.len())?;
+
+    let sym: Sym<S> = (&header[0]).try_into()?;
+    Ok((sym.as_symbol(), header[1].clone(), header.get(2).cloned()))
+  }
+
+  /// Run a loop binding `name` to each of `items` in turn, then evaluate the
+  /// optional result form with `name` bound to `last`.
+  fn eval_loop_over<I: Iterator<Item = Value<S>>>(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    name: S,
+    items: I,
+    last: Value<S>,
+    result: Option<Value<S>>,
+    body: &[Value<S>],
+  ) -> Result<Value<S>> {
+    for item in items {
+      let scope = Rc::new(RefCell::new(Env::extend(env.clone())));
+      scope.borrow_mut().define(name, item);
+
+      if !self.eval_loop_body(scope, body)? {
+        return Ok(self.loop_result());
+      }
+    }
+
+    match result {
+      Some(result) => {
+        let scope = Rc::new(RefCell::new(Env::extend(env)));
+        scope.borrow_mut().define(name, last);
+        self.eval_expression(scope, result)
+      },
+      None => Ok(Value::default()),
+    }
+  }
+
+  /// `(dotimes (i count [result]) body ...)` runs the body with `i` bound
+  /// to `0` up to `count - 1`.
+  pub fn builtin_loop_dotimes(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(1, args.len())?;
+
+    let (name, count, result) = self.parse_loop_header(&args[0])?;
+    let count: i64 = self.eval_expression(env.clone(), count)?.try_into()?;
+    let count = count.max(0);
+
+    self.eval_loop_over(
+      env,
+      name,
+      (0..count).map(Value::Integer),
+      Value::Integer(count),
+      result,
+      &args[1..],
+    )
+  }
+
+  /// `(dolist (item seq [result]) body ...)` runs the body for each element
+  /// of a list or vector, `item` being nil in the result form.
+  pub fn builtin_loop_dolist(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(1, args.len())?;
+
+    let (name, seq, result) = self.parse_loop_header(&args[0])?;
+
+    let items: Vec<Value<S>> = match self.eval_expression(env.clone(), seq)? {
+      Value::List(list) => list.into_iter().collect(),
+      Value::Vector(items) => items.borrow().clone(),
+      Value::PersistentVector(items) => items.iter().cloned().collect(),
+      val => return Err(Type::error(val.as_type(), Type::List)),
     };
 
-    self.eval_expression(env.clone(), branch.clone())
+    self.eval_loop_over(env, name, items.into_iter(), Value::default(), result, &args[1..])
   }
 
-  pub fn builtin_lambda(&mut self, args: Vec<Value<S>>) -> Result<Value<S>> {
-    assert_exactly_args(2, args.len())?;
+  /// `(do ((var init [step]) ...) (test result ...) body ...)`: bind every
+  /// `var` to its `init`, then until `test` is truthy run the body and
+  /// rebind each `var` to its `step`. Yields the last `result`, or nil.
+  pub fn builtin_loop_do(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(2, args.len())?;
 
-    let params = &args[0];
-    let body = &args[1];
+    let specs: List<S> = (&args[0]).try_into()?;
+    let mut vars = Vec::new();
+    let scope = Rc::new(RefCell::new(Env::extend(env.clone())));
 
-    let params: List<S> = params.try_into()?;
-    let mut param_names = Vec::new();
+    for spec in specs.into_iter() {
+      let spec: List<S> = spec.try_into()?;
+      let spec: Vec<Value<S>> = spec.into_iter().collect();
+      assert_args_between(2, Some(3), spec.len())?;
 
-    for param in params.into_iter() {
-      let param_name: Sym<S> = param.try_into()?;
-      param_names.push(param_name.as_symbol());
+      let sym: Sym<S> = (&spec[0]).try_into()?;
+      let init = self.eval_expression(env.clone(), spec[1].clone())?;
+      scope.borrow_mut().define(sym.as_symbol(), init);
+      vars.push((sym.as_symbol(), spec.get(2).cloned()));
     }
 
-    let lambda = Value::Function(Function::Lambda(Lambda {
-      params: param_names,
-      body: Box::new(body.clone()),
-    }));
+    let exit: List<S> = (&args[1]).try_into()?;
+    let exit: Vec<Value<S>> = exit.into_iter().collect();
+    assert_at_least_args(1, exit.len())?;
 
-    Ok(lambda)
+    let mut scope = scope;
+
+    loop {
+      if self.eval_expression(scope.clone(), exit[0].clone())?.is_truthy() {
+        return self.eval_body(scope, &exit[1..]);
+      }
+
+      let body_scope = Rc::new(RefCell::new(Env::extend(scope.clone())));
+
+      if !self.eval_loop_body(body_scope, &args[2..])? {
+        return Ok(self.loop_result());
+      }
+
+      let next = Rc::new(RefCell::new(Env::extend(env.clone())));
+
+      for (sym, step) in &vars {
+        let val = match step {
+          Some(step) => self.eval_expression(scope.clone(), step.clone())?,
+          None => scope.borrow().get(*sym).unwrap_or_default(),
+        };
+
+        next.borrow_mut().define(*sym, val);
+      }
+
+      scope = next;
+    }
   }
 
-  pub fn builtin_let_expression(
+  /// `(break [value])` exits the innermost loop, which yields `value`.
+  pub fn builtin_loop_break(
     &mut self,
     env: Rc<RefCell<Env<S>>>,
     args: Vec<Value<S>>,
   ) -> Result<Value<S>> {
-    assert_exactly_args(2, args.len())?;
-    let decls = &args[0];
-    let body = &args[1];
+    assert_args_between(0, Some(1), args.len())?;
+
+    let value = match args.first() {
+      Some(value) => self.eval_expression(env, value.clone())?,
+      None => Value::default(),
+    };
+
+    self.break_value = Some(value);
+    Err(RuntimeError::Break)
+  }
+
+  /// `(continue)` skips to the next iteration of the innermost loop.
+  pub fn builtin_loop_continue(&mut self, args: Vec<Value<S>>) -> Result<Value<S>> {
+    assert_exactly_args(0, args.len())?;
+    Err(RuntimeError::Continue)
+  }
+
+  fn invalid_param(&self, param: &Value<S>) -> RuntimeError {
+    RuntimeError::TypeError {
+      expected: "Parameter".to_string(),
+      got: self.format_value(param),
+    }
+  }
+
+  /// Parse `name` or `(name default)`, the default being nil when omitted.
+  fn parse_param_with_default(&self, param: &Value<S>) -> Result<(S, Value<S>)> {
+    match param {
+      Value::Symbol(sym) => Ok((sym.as_symbol(), Value::default())),
+      Value::List(list) => {
+        let parts: Vec<Value<S>> = list.into_iter().collect();
+
+        match parts.as_slice() {
+          [Value::Symbol(sym), default] => Ok((sym.as_symbol(), default.clone())),
+          _ => Err(self.invalid_param(param)),
+        }
+      },
+      _ => Err(self.invalid_param(param)),
+    }
+  }
+
+  fn parse_params(&self, params: &Value<S>) -> Result<Params<S>> {
+    enum Section { Required, Optional, Rest, Key }
+
+    let list: List<S> = params.try_into()?;
+    let mut result = Params::positional(Vec::new());
+    let mut section = Section::Required;
+
+    for param in list.into_iter() {
+      let marker = match &param {
+        Value::Symbol(sym) => self.interner.resolve(sym.as_symbol()),
+        _ => None,
+      };
+
+      match (marker, &section) {
+        (Some("&optional"), Section::Required) => {
+          section = Section::Optional;
+          continue;
+        },
+        (Some("&rest" | "."), Section::Required | Section::Optional) => {
+          section = Section::Rest;
+          continue;
+        },
+        (Some("&key"), Section::Required | Section::Optional | Section::Rest) => {
+          if matches!(section, Section::Rest) && result.rest.is_none() {
+            return Err(self.invalid_param(&param));
+          }
+
+          section = Section::Key;
+          continue;
+        },
+        (Some("&optional" | "&rest" | "." | "&key"), _) => {
+          return Err(self.invalid_param(&param));
+        },
+        _ => {},
+      }
+
+      match section {
+        Section::Required => {
+          result.required.push(param);
+        },
+        Section::Optional => {
+          result.optional.push(self.parse_param_with_default(&param)?);
+        },
+        Section::Rest => {
+          if result.rest.is_some() {
+            return Err(self.invalid_param(&param));
+          }
+
+          let sym: Sym<S> = (&param).try_into()?;
+          result.rest = Some(sym.as_symbol());
+        },
+        Section::Key => {
+          result.keys.push(self.parse_param_with_default(&param)?);
+        },
+      }
+    }
+
+    if matches!(section, Section::Rest) && result.rest.is_none() {
+      return Err(self.invalid_param(params));
+    }
+
+    Ok(result)
+  }
 
+  /// Split `((pattern expr) ...)` into its patterns and unevaluated
+  /// expressions.
+  fn parse_bindings(&self, decls: &Value<S>) -> Result<Vec<(Value<S>, Value<S>)>> {
     let decls: List<S> = decls.try_into()?;
-    let mut params = Vec::new();
-    let mut args = Vec::new();
+    let mut bindings = Vec::new();
 
     for decl in decls.into_iter() {
       let decl: List<S> = decl.try_into()?;
       let decl: Vec<Value<S>> = decl.into_iter().collect();
       assert_exactly_args(2, decl.len())?;
 
-      let sym = &decl[0];
-      let val = &decl[1];
+      bindings.push((decl[0].clone(), decl[1].clone()));
+    }
+
+    Ok(bindings)
+  }
+
+  /// `(lambda (param ...) body ...)`
+  pub fn builtin_lambda(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(2, args.len())?;
+
+    let lambda = Value::Function(Function::Lambda(Rc::new(Lambda {
+      name: None,
+      params: self.parse_params(&args[0])?,
+      body: args[1..].to_vec(),
+      env,
+    })));
+
+    Ok(lambda)
+  }
+
+  /// `(let ((name expr) ...) body ...)` evaluates every expression in the
+  /// outer scope before binding. The named form `(let loop ((name expr) ...)
+  /// body ...)` also binds `loop` to a function of the names, so the body
+  /// can iterate by calling it.
+  pub fn builtin_let_expression(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(2, args.len())?;
+
+    if let Value::Symbol(name) = &args[0] {
+      return self.builtin_named_let_expression(env, name.as_symbol(), args[1..].to_vec());
+    }
+
+    let bindings = self.parse_bindings(&args[0])?;
+    let scope = Rc::new(RefCell::new(Env::extend(env.clone())));
+
+    for (pattern, val) in bindings {
+      let val = self.eval_expression(env.clone(), val)?;
+      self.bind_pattern(&scope, &pattern, val)?;
+    }
+
+    self.eval_body(scope, &args[1..])
+  }
+
+  fn builtin_named_let_expression(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    name: S,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(2, args.len())?;
+
+    let bindings = self.parse_bindings(&args[0])?;
+    let scope = Rc::new(RefCell::new(Env::extend(env.clone())));
+    let mut params = Vec::with_capacity(bindings.len());
+    let mut vals = Vec::with_capacity(bindings.len());
+
+    for (pattern, val) in bindings {
+      params.push(pattern);
+      vals.push(self.eval_expression(env.clone(), val)?);
+    }
+
+    let lambda = Function::Lambda(Rc::new(Lambda {
+      name: Some(name),
+      params: Params::positional(params),
+      body: args[1..].to_vec(),
+      env: scope.clone(),
+    }));
+
+    scope.borrow_mut().define(name, Value::Function(lambda.clone()));
+    self.eval_function(scope, lambda, vals)
+  }
+
+  /// `(let* ((name expr) ...) body ...)` binds sequentially, each expression
+  /// sees the names bound before it.
+  pub fn builtin_let_star_expression(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(2, args.len())?;
+
+    let bindings = self.parse_bindings(&args[0])?;
+    let mut scope = env;
+
+    for (pattern, val) in bindings {
+      let val = self.eval_expression(scope.clone(), val)?;
+      scope = Rc::new(RefCell::new(Env::extend(scope)));
+      self.bind_pattern(&scope, &pattern, val)?;
+    }
+
+    self.eval_body(scope, &args[1..])
+  }
+
+  /// `(letrec ((name expr) ...) body ...)` evaluates every expression in a
+  /// scope where all names are already bound (to nil until assigned), so
+  /// local functions can refer to each other. `letrec` assigns once every
+  /// expression has been evaluated, `letrec*` assigns each name in turn.
+  pub fn builtin_letrec_expression(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+    sequential: bool,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(2, args.len())?;
+
+    let mut bindings = Vec::new();
 
-      let sym: Sym<S> = sym.try_into()?;
-      let val = self.eval_expression(env.clone(), val.clone())?;
+    for (pattern, val) in self.parse_bindings(&args[0])? {
+      let sym: Sym<S> = pattern.try_into()?;
+      bindings.push((sym.as_symbol(), val));
+    }
+
+    let scope = Rc::new(RefCell::new(Env::extend(env)));
 
-      params.push(sym.as_symbol());
-      args.push(val);
+    for (sym, _) in &bindings {
+      scope.borrow_mut().define(*sym, Value::default());
     }
 
-    let lambda = Function::Lambda(Lambda {
-      params,
-      body: Box::new(body.clone()),
-    });
+    let mut vals = Vec::with_capacity(bindings.len());
+
+    for (sym, val) in bindings {
+      let val = self.eval_expression(scope.clone(), val)?;
+
+      if sequential {
+        scope.borrow_mut().define(sym, val);
+      }
+      else {
+        vals.push((sym, val));
+      }
+    }
+
+    for (sym, val) in vals {
+      scope.borrow_mut().define(sym, val);
+    }
 
-    self.eval_function(env.clone(), lambda, args)
+    self.eval_body(scope, &args[1..])
   }
 }
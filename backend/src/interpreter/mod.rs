@@ -1,68 +1,315 @@
-use std::{rc::Rc, cell::RefCell};
+use std::{
+  collections::HashMap,
+  path::{Path, PathBuf},
+  rc::Rc,
+  cell::RefCell,
+};
 
 use lispers_common::{StringInterner, Backend, Symbol};
 use lispers_frontend::{SExpression, Literal};
 use crate::prelude::*;
-use crate::data::{Value, List, Function};
-use crate::env::{Env, default_env};
+use crate::data::{Value, Type, List, Function, Params, Port, Context};
+use crate::env::{Env, Capability, default_env, PRELUDE};
 
-use crate::utils::assert_exactly_args;
+use crate::utils::assert_args_between;
 
 mod builtins;
+mod pattern;
+mod module;
+
+use module::{Module, ModuleFrame};
+
+#[cfg(feature = "serde")]
+use crate::env::EnvBuilder;
+#[cfg(feature = "serde")]
+use crate::serialization::{Serializable, ValueDeserializer};
 
 pub struct Interpreter<S: Symbol, B: Backend<S>> {
   interner: StringInterner<B>,
+  /// Value given to the `break` currently unwinding to its loop.
+  break_value: Option<Value<S>>,
+  /// File currently being evaluated, imports are resolved relative to it.
+  current_file: Option<PathBuf>,
+  /// Directories searched for imports not found next to the importing file.
+  search_paths: Vec<PathBuf>,
+  /// Modules already loaded, by canonical path and capabilities denied to
+  /// them.
+  modules: HashMap<(PathBuf, Vec<Capability>), Rc<Module<S>>>,
+  /// Modules being loaded, innermost last, to detect cyclic imports.
+  loading: Vec<ModuleFrame<S>>,
+  /// Where `read-line` and friends read from.
+  input: Port,
+  /// Where `println` and friends write, see `with-output-to-string`.
+  output: Port,
   marker: std::marker::PhantomData<S>,
 }
 
+/// Floats always read back as floats: integral ones get a `.0`, and
+/// infinities and NaN are spelled `+inf.0`, `-inf.0`, `+nan.0` or `-nan.0`.
+fn write_float(val: f64) -> String {
+  let sign = if val.is_sign_negative() { '-' } else { '+' };
+  let repr = format!("{}", val);
+
+  if val.is_nan() {
+    format!("{}nan.0", sign)
+  }
+  else if val.is_infinite() {
+    format!("{}inf.0", sign)
+  }
+  else if !repr.contains(['.', 'e']) {
+    format!("{}.0", repr)
+  }
+  else {
+    repr
+  }
+}
+
+fn write_char(val: char) -> String {
+  match val {
+    ' ' => "#\\space".to_string(),
+    '\n' => "#\\newline".to_string(),
+    '\t' => "#\\tab".to_string(),
+    '\r' => "#\\return".to_string(),
+    '\0' => "#\\nul".to_string(),
+    c if c.is_whitespace() || c.is_control() => format!("#\\x{:x}", c as u32),
+    c => format!("#\\{}", c),
+  }
+}
+
+fn write_string(val: &str) -> String {
+  let mut repr = String::with_capacity(val.len() + 2);
+  repr.push('"');
+
+  for c in val.chars() {
+    match c {
+      '"' => repr.push_str("\\\""),
+      '\\' => repr.push_str("\\\\"),
+      '\n' => repr.push_str("\\n"),
+      '\t' => repr.push_str("\\t"),
+      '\r' => repr.push_str("\\r"),
+      c if c.is_control() => repr.push_str(&format!("\\u{{{:x}}}", c as u32)),
+      c => repr.push(c),
+    }
+  }
+
+  repr.push('"');
+  repr
+}
+
+impl<S: Symbol, B: Backend<S>> Default for Interpreter<S, B> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
   pub fn new() -> Self {
     Self {
       interner: StringInterner::new(),
+      break_value: None,
+      current_file: None,
+      search_paths: Vec::new(),
+      modules: HashMap::new(),
+      loading: Vec::new(),
+      input: Port::stdin(),
+      output: Port::stdout(),
       marker: std::marker::PhantomData{},
     }
   }
 
+  /// Same as `standard_env`.
   pub fn default_env(&mut self) -> Rc<RefCell<Env<S>>> {
+    self.standard_env()
+  }
+
+  /// Native functions and the Lisp prelude, with every capability: see
+  /// `EnvBuilder` for envs without `exit` or I/O.
+  pub fn standard_env(&mut self) -> Rc<RefCell<Env<S>>> {
+    let env = self.bare_env();
+    self.load_prelude(env.clone());
+    env
+  }
+
+  pub(crate) fn load_prelude(&mut self, env: Rc<RefCell<Env<S>>>) {
+    if let Err(err) = self.eval_string(env, PRELUDE) {
+      panic!("prelude.lisp failed to evaluate: {}", err);
+    }
+  }
+
+  /// Native functions only, without the prelude.
+  pub fn bare_env(&mut self) -> Rc<RefCell<Env<S>>> {
     Rc::new(RefCell::new(default_env(&mut self.interner)))
   }
 
+  /// Human-readable representation, as printed by `println` and `display`.
   pub fn format_value(&self, value: &Value<S>) -> String {
+    self.repr(value, false)
+  }
+
+  /// Machine-readable representation, as printed by `write`: data values
+  /// read back with `read` are `equal?` to the original.
+  pub fn write_value(&self, value: &Value<S>) -> String {
+    self.repr(value, true)
+  }
+
+  /// Serialize a value, naming its symbols and keywords.
+  #[cfg(feature = "serde")]
+  pub fn serializable<'a>(&'a self, value: &'a Value<S>) -> Serializable<'a, S> {
+    Serializable::new(value, Some(&self.interner))
+  }
+
+  /// Deserializer reading Rust types out of a value, symbols and keywords
+  /// are read as their name.
+  #[cfg(feature = "serde")]
+  pub fn deserializer(&self, value: &Value<S>) -> ValueDeserializer<'_, S> {
+    ValueDeserializer::new(value.clone(), Some(&self.interner))
+  }
+
+  #[cfg(feature = "serde")]
+  pub fn from_value<T: serde::de::DeserializeOwned>(
+    &self,
+    value: &Value<S>,
+  ) -> std::result::Result<T, crate::serialization::Error> {
+    T::deserialize(self.deserializer(value))
+  }
+
+  fn repr(&self, value: &Value<S>, readable: bool) -> String {
     match value {
       Value::Boolean(val) => format!("{}", val),
       Value::Integer(val) => format!("{}", val),
+      Value::Float(val) if readable => write_float(*val),
       Value::Float(val) => format!("{}", val),
-      Value::String(val) => format!("{}", val),
+      Value::Char(val) if readable => write_char(*val),
+      Value::Char(val) => val.to_string(),
+      Value::String(val) if readable => write_string(val),
+      Value::String(val) => val.clone(),
       Value::Symbol(sym) => {
         self.interner.resolve(sym.as_symbol()).unwrap_or("<>").to_string()
       },
+      Value::Keyword(sym) => {
+        format!(":{}", self.interner.resolve(sym.as_symbol()).unwrap_or("<>"))
+      },
       Value::List(list) => {
         let repr = list
           .into_iter()
-          .map(|value| self.format_value(&value))
+          .map(|value| self.repr(&value, readable))
           .collect::<Vec<String>>()
           .join(" ");
 
         format!("({})", repr)
       },
+      Value::Vector(items) => {
+        let repr = items
+          .borrow()
+          .iter()
+          .map(|value| self.repr(value, readable))
+          .collect::<Vec<String>>()
+          .join(" ");
+
+        format!("[{}]", repr)
+      },
+      Value::Map(map) => {
+        let repr = map
+          .iter()
+          .map(|(key, val)| {
+            format!("{} {}", self.repr(key, readable), self.repr(val, readable))
+          })
+          .collect::<Vec<String>>()
+          .join(" ");
+
+        format!("{{{}}}", repr)
+      },
+      Value::PersistentVector(items) => {
+        let repr = items
+          .iter()
+          .map(|value| self.repr(value, readable))
+          .collect::<Vec<String>>()
+          .join(" ");
+
+        format!("[{}]", repr)
+      },
+      Value::PersistentMap(map) => {
+        let repr = map
+          .iter()
+          .map(|(key, val)| {
+            format!("{} {}", self.repr(key, readable), self.repr(val, readable))
+          })
+          .collect::<Vec<String>>()
+          .join(" ");
+
+        format!("{{{}}}", repr)
+      },
       Value::Function(func) => match func {
-        Function::NativeFn(native_func) => {
-          format!("[function {:p}]", native_func)
+        Function::NativeFn { name, .. } => {
+          format!("[function {}]", self.interner.resolve(*name).unwrap_or("<>"))
         },
-        Function::Lambda(lambda) => {
-          format!("[function {:p}]", lambda)
+        Function::Lambda(lambda) => match lambda.name {
+          Some(name) => {
+            format!("[function {}]", self.interner.resolve(name).unwrap_or("<>"))
+          },
+          None => format!("[function {:p}]", Rc::as_ptr(lambda)),
         },
       },
+      Value::Port(port) => format!("[port {}]", port.name()),
     }
   }
 
-  pub fn eval_file<P: AsRef<std::path::Path>>(
+  /// Deny I/O, file system and process control: the returned env lacks
+  /// those capabilities, and the default ports are closed for good, so this
+  /// is only meant for an interpreter dedicated to the sandbox.
+  #[cfg(feature = "serde")]
+  pub(crate) fn sandbox(&mut self) -> Rc<RefCell<Env<S>>> {
+    self.input = Port::closed("sandbox");
+    self.output = Port::closed("sandbox");
+
+    EnvBuilder::new()
+      .without(Capability::Io)
+      .without(Capability::Fs)
+      .without(Capability::Process)
+      .build(self)
+  }
+
+  #[cfg(feature = "serde")]
+  pub(crate) fn symbol_name(&self, sym: S) -> Option<&str> {
+    self.interner.resolve(sym)
+  }
+
+  /// Parse `input` without evaluating it, naming `filename` in syntax errors.
+  pub(crate) fn parse_source(
+    &mut self,
+    filename: Option<PathBuf>,
+    input: &str,
+  ) -> Result<Vec<SExpression<S>>> {
+    Ok(lispers_frontend::parse(filename, input, &mut self.interner)?)
+  }
+
+  pub(crate) fn eval_sexpression(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    sexpression: &SExpression<S>,
+  ) -> Result<Value<S>> {
+    let expression = self.parse_sexpression(sexpression)?;
+    self.eval_expression(env, expression)
+  }
+
+  /// Add a directory in which `import` looks for modules.
+  pub fn add_search_path<P: AsRef<Path>>(&mut self, path: P) {
+    self.search_paths.push(path.as_ref().to_path_buf());
+  }
+
+  pub fn eval_file<P: AsRef<Path>>(
     &mut self,
     env: Rc<RefCell<Env<S>>>,
     input_path: P,
   ) -> Result<Value<S>> {
-    let input = std::fs::read_to_string(input_path)?;
-    self.eval_string(env, &input)
+    let path = input_path.as_ref().to_path_buf();
+    let input = std::fs::read_to_string(&path)?;
+
+    let previous = self.current_file.replace(path.clone());
+    let result = self.eval_source(env, Some(path), &input);
+    self.current_file = previous;
+
+    result
   }
 
   pub fn eval_string(
@@ -70,22 +317,39 @@ impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
     env: Rc<RefCell<Env<S>>>,
     input: &str,
   ) -> Result<Value<S>> {
-    let sexpressions = lispers_frontend::parse(
-      None,
-      &input,
-      &mut self.interner,
-    )?;
+    self.eval_source(env, None, input)
+  }
 
+  /// Parse and evaluate `input`, naming `filename` in syntax errors.
+  fn eval_source(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    filename: Option<PathBuf>,
+    input: &str,
+  ) -> Result<Value<S>> {
+    let sexpressions = self.parse_source(filename, input)?;
     let mut last_result = Value::default();
 
     for sexpression in sexpressions.iter() {
-      let expression = self.parse_sexpression(sexpression)?;
-      last_result = self.eval_expression(env.clone(), expression)?;
+      last_result = self.eval_sexpression(env.clone(), sexpression)?;
     }
 
     Ok(last_result)
   }
 
+  /// Error for a symbol without value: `PermissionDenied` for the builtins
+  /// of a denied capability, `UndefinedSymbol` otherwise.
+  fn undefined(&self, env: &Rc<RefCell<Env<S>>>, sym: S) -> RuntimeError {
+    let name = self.interner.resolve(sym).unwrap_or("<>").to_string();
+
+    match env.borrow().denied(sym) {
+      Some(capability) => RuntimeError::PermissionDenied {
+        detail: format!("{} requires the {} capability", name, capability),
+      },
+      None => RuntimeError::UndefinedSymbol { detail: name },
+    }
+  }
+
   pub fn eval_expression(
     &mut self,
     env: Rc<RefCell<Env<S>>>,
@@ -94,9 +358,8 @@ impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
     match expression {
       Value::Symbol(sym) => {
         let sym = sym.as_symbol();
-        env.borrow().get(sym).ok_or_else(|| RuntimeError::UndefinedSymbol {
-          detail: self.interner.resolve(sym).unwrap_or("<>").to_string()
-        })
+        let val = env.borrow().get(sym);
+        val.ok_or_else(|| self.undefined(&env, sym))
       },
       Value::List(list) if !list.empty() => {
         let func = list.car()?;
@@ -106,20 +369,42 @@ impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
           let sym = sym.as_symbol();
           let func_name = self.interner.resolve(sym).unwrap_or("<>");
 
+          if matches!(func_name, "println" | "load" | "import") && env.borrow().denied(sym).is_some() {
+            return Err(self.undefined(&env, sym));
+          }
+
           match func_name {
             "println" => return self.builtin_println(env.clone(), args),
             "quote" => return self.builtin_quote(args),
             "def" => return self.builtin_define(env.clone(), args),
             "set!" => return self.builtin_set(env.clone(), args),
             "if" => return self.builtin_controlflow_if(env.clone(), args),
-            "lambda" => return self.builtin_lambda(args),
+            "and" => return self.builtin_controlflow_and(env.clone(), args),
+            "or" => return self.builtin_controlflow_or(env.clone(), args),
+            "cond" => return self.builtin_controlflow_cond(env.clone(), args),
+            "case" => return self.builtin_controlflow_case(env.clone(), args),
+            "match" => return self.builtin_match(env.clone(), args),
+            "while" => return self.builtin_loop_while(env.clone(), args),
+            "dotimes" => return self.builtin_loop_dotimes(env.clone(), args),
+            "dolist" => return self.builtin_loop_dolist(env.clone(), args),
+            "do" => return self.builtin_loop_do(env.clone(), args),
+            "break" => return self.builtin_loop_break(env.clone(), args),
+            "continue" => return self.builtin_loop_continue(args),
+            "module" => return self.builtin_module(args),
+            "import" => return self.builtin_import(env.clone(), args),
+            "load" => return self.builtin_load(env.clone(), args),
+            "when" => return self.builtin_controlflow_when(env.clone(), args, true),
+            "unless" => return self.builtin_controlflow_when(env.clone(), args, false),
+            "lambda" => return self.builtin_lambda(env.clone(), args),
             "let" => return self.builtin_let_expression(env.clone(), args),
+            "let*" => return self.builtin_let_star_expression(env.clone(), args),
+            "letrec" => return self.builtin_letrec_expression(env.clone(), args, false),
+            "letrec*" => return self.builtin_letrec_expression(env.clone(), args, true),
             _ => {},
           }
         }
 
         let func = self.eval_expression(env.clone(), func)?;
-        let func: Function<S> = func.try_into()?;
 
         let mut eval_args = Vec::with_capacity(args.len());
 
@@ -128,7 +413,30 @@ impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
           eval_args.push(arg);
         }
 
-        self.eval_function(env.clone(), func, eval_args)
+        self.apply(env.clone(), func, eval_args)
+      },
+      Value::Vector(items) => {
+        let items = items.borrow().clone();
+        let mut eval_items = Vec::with_capacity(items.len());
+
+        for item in items {
+          let item = self.eval_expression(env.clone(), item)?;
+          eval_items.push(item);
+        }
+
+        Ok(Value::Vector(Rc::new(RefCell::new(eval_items))))
+      },
+      Value::Map(entries) => {
+        let mut eval_entries = HashMap::with_capacity(entries.len());
+
+        for (key, val) in entries.iter() {
+          let key = self.eval_expression(env.clone(), key.clone())?;
+          key.assert_hashable()?;
+          let val = self.eval_expression(env.clone(), val.clone())?;
+          eval_entries.insert(key, val);
+        }
+
+        Ok(Value::Map(Rc::new(eval_entries)))
       },
       _ => {
         Ok(expression)
@@ -136,33 +444,145 @@ impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
     }
   }
 
+  /// Evaluate a sequence of forms, yielding the value of the last one (or
+  /// nil when there are none).
+  fn eval_body(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    body: &[Value<S>],
+  ) -> Result<Value<S>> {
+    let mut result = Value::default();
+
+    for form in body {
+      result = self.eval_expression(env.clone(), form.clone())?;
+    }
+
+    Ok(result)
+  }
+
+  fn is_symbol_named(&self, val: &Value<S>, name: &str) -> bool {
+    match val {
+      Value::Symbol(sym) => self.interner.resolve(sym.as_symbol()) == Some(name),
+      _ => false,
+    }
+  }
+
+  /// Bind already arity-checked arguments to their parameters in `scope`.
+  fn bind_params(
+    &mut self,
+    scope: Rc<RefCell<Env<S>>>,
+    params: &Params<S>,
+    args: Vec<Value<S>>,
+  ) -> Result<()> {
+    let mut args = args.into_iter();
+
+    for (pattern, arg) in std::iter::zip(&params.required, args.by_ref()) {
+      self.bind_pattern(&scope, pattern, arg)?;
+    }
+
+    for (name, default) in &params.optional {
+      let val = match args.next() {
+        Some(arg) => arg,
+        None => self.eval_expression(scope.clone(), default.clone())?,
+      };
+
+      scope.borrow_mut().define(*name, val);
+    }
+
+    let rest: Vec<Value<S>> = args.collect();
+
+    if let Some(name) = params.rest {
+      let list = rest.iter().cloned().collect();
+      scope.borrow_mut().define(name, Value::List(list));
+    }
+
+    if params.keys.is_empty() {
+      return Ok(());
+    }
+
+    let mut given = HashMap::new();
+
+    for pair in rest.chunks(2) {
+      match pair {
+        [Value::Keyword(key), val] if params.keys.iter().any(|(name, _)| *name == key.as_symbol()) => {
+          given.entry(key.as_symbol()).or_insert_with(|| val.clone());
+        },
+        [_, _] if params.rest.is_some() => {},
+        _ => {
+          return Err(RuntimeError::TypeError {
+            expected: "KeywordArgument".to_string(),
+            got: self.format_value(&pair[0]),
+          });
+        },
+      }
+    }
+
+    for (name, default) in &params.keys {
+      let val = match given.remove(name) {
+        Some(val) => val,
+        None => self.eval_expression(scope.clone(), default.clone())?,
+      };
+
+      scope.borrow_mut().define(*name, val);
+    }
+
+    Ok(())
+  }
+
+  /// `break` and `continue` are lexical: they do not unwind through a
+  /// function call to a loop around the caller.
   fn eval_function(
     &mut self,
     env: Rc<RefCell<Env<S>>>,
     func: Function<S>,
     args: Vec<Value<S>>,
   ) -> Result<Value<S>> {
-    match func {
-      Function::NativeFn(func) => {
-        func(env.clone(), args)
+    let result = match func {
+      Function::NativeFn { func, .. } => {
+        func(self, env, args)
       },
       Function::Lambda(lambda) => {
-        assert_exactly_args(lambda.params.len(), args.len())?;
-
-        let mut env = Env::extend(env.clone());
+        let (min, max) = lambda.params.arity();
+        assert_args_between(min, max, args.len())?;
 
-        for (name, arg) in std::iter::zip(lambda.params, args) {
-          env.define(name, arg);
-        }
+        let scope = Rc::new(RefCell::new(Env::extend(lambda.env.clone())));
+        self.bind_params(scope.clone(), &lambda.params, args)?;
+        self.eval_body(scope, &lambda.body)
+      },
+    };
 
-        self.eval_expression(
-          Rc::new(RefCell::new(env)),
-          lambda.body.as_ref().clone(),
-        )
+    match result {
+      Err(RuntimeError::Break) => {
+        self.break_value = None;
+        Err(RuntimeError::LoopError { detail: "break outside of a loop".to_string() })
+      },
+      Err(RuntimeError::Continue) => {
+        Err(RuntimeError::LoopError { detail: "continue outside of a loop".to_string() })
       },
+      result => result,
     }
   }
 
+  fn eval_keyword_lookup(
+    &mut self,
+    keyword: Value<S>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_args_between(1, Some(2), args.len())?;
+
+    let default = args.get(1).cloned().unwrap_or_default();
+
+    let val = match &args[0] {
+      Value::Map(map) => map.get(&keyword).cloned(),
+      Value::PersistentMap(map) => map.get(&keyword).cloned(),
+      val => {
+        return Err(Type::error(val.as_type(), Type::Map));
+      },
+    };
+
+    Ok(val.unwrap_or(default))
+  }
+
   fn parse_sexpression(&self, sexpression: &SExpression<S>) -> Result<Value<S>> {
     match sexpression {
       SExpression::Literal(val) => {
@@ -177,7 +597,27 @@ impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
         }
 
         Ok(Value::List(list))
-      }
+      },
+      SExpression::Vector(elements) => {
+        let mut items = Vec::with_capacity(elements.len());
+
+        for element in elements.iter() {
+          items.push(self.parse_sexpression(element)?);
+        }
+
+        Ok(Value::Vector(Rc::new(RefCell::new(items))))
+      },
+      SExpression::Map(entries) => {
+        let mut map = HashMap::with_capacity(entries.len());
+
+        for (key, val) in entries.iter() {
+          let key = self.parse_sexpression(key)?;
+          let val = self.parse_sexpression(val)?;
+          map.insert(key, val);
+        }
+
+        Ok(Value::Map(Rc::new(map)))
+      },
     }
   }
 
@@ -186,8 +626,63 @@ impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
       Literal::Boolean(val) => Value::Boolean(*val),
       Literal::Integer(val) => Value::Integer(*val),
       Literal::Float(val) => Value::Float(*val),
+      Literal::Char(val) => Value::Char(*val),
       Literal::String(val) => Value::String(val.clone()),
       Literal::Symbol(sym) => Value::Symbol(sym.into()),
+      Literal::Keyword(sym) => Value::Keyword(sym.into()),
+    }
+  }
+}
+
+impl<S: Symbol, B: Backend<S>> Context<S> for Interpreter<S, B> {
+  fn apply(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    func: Value<S>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    if let Value::Keyword(..) = func {
+      return self.eval_keyword_lookup(func, args);
+    }
+
+    let func: Function<S> = func.try_into()?;
+    self.eval_function(env, func, args)
+  }
+
+  fn intern(&mut self, name: &str) -> S {
+    self.interner.get_or_intern(name)
+  }
+
+  fn read(&mut self, input: &str) -> Result<Vec<Value<S>>> {
+    let sexpressions = lispers_frontend::parse(None, input, &mut self.interner)?;
+    let mut data = Vec::with_capacity(sexpressions.len());
+
+    for sexpression in sexpressions.iter() {
+      let datum = self.parse_sexpression(sexpression)?;
+      datum.assert_hashable_keys()?;
+      data.push(datum);
     }
+
+    Ok(data)
+  }
+
+  fn display(&self, value: &Value<S>) -> String {
+    self.format_value(value)
+  }
+
+  fn write(&self, value: &Value<S>) -> String {
+    self.write_value(value)
+  }
+
+  fn input_port(&self) -> Port {
+    self.input.clone()
+  }
+
+  fn output_port(&self) -> Port {
+    self.output.clone()
+  }
+
+  fn replace_output_port(&mut self, port: Port) -> Port {
+    std::mem::replace(&mut self.output, port)
   }
 }
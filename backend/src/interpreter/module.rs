@@ -0,0 +1,240 @@
+use std::{
+  path::{Path, PathBuf},
+  rc::Rc,
+  cell::RefCell,
+};
+use lispers_common::{Backend, Symbol};
+
+use crate::prelude::*;
+use crate::data::{Value, Sym, List};
+use crate::env::{Env, EnvBuilder, Capability};
+use super::Interpreter;
+
+use crate::utils::{assert_exactly_args, assert_at_least_args};
+
+/// A loaded module: the environment its file was evaluated in, and the
+/// symbols importers may bind.
+pub(super) struct Module<S: Symbol> {
+  env: Rc<RefCell<Env<S>>>,
+  exports: Vec<S>,
+}
+
+/// A module whose file is being evaluated. `exports` is filled by its
+/// `module` form, if any.
+pub(super) struct ModuleFrame<S: Symbol> {
+  path: PathBuf,
+  exports: Option<Vec<S>>,
+}
+
+impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
+  /// `(module name (export f g ...))` declares what the file being imported
+  /// exports. Without it, every top-level definition is exported.
+  pub fn builtin_module(&mut self, args: Vec<Value<S>>) -> Result<Value<S>> {
+    assert_at_least_args(1, args.len())?;
+
+    let _name: Sym<S> = (&args[0]).try_into()?;
+    let mut exports = Vec::new();
+
+    for clause in &args[1..] {
+      let clause: List<S> = clause.try_into()?;
+      let clause: Vec<Value<S>> = clause.into_iter().collect();
+
+      if clause.is_empty() || !self.is_symbol_named(&clause[0], "export") {
+        return Err(RuntimeError::TypeError {
+          expected: "ExportClause".to_string(),
+          got: self.format_value(&Value::List(clause.into_iter().collect())),
+        });
+      }
+
+      for name in &clause[1..] {
+        let sym: Sym<S> = name.try_into()?;
+        exports.push(sym.as_symbol());
+      }
+    }
+
+    if let Some(frame) = self.loading.last_mut() {
+      frame.exports = Some(exports);
+    }
+
+    Ok(Value::default())
+  }
+
+  /// `(import "path" (only f g ...) (prefix p:))` loads a module (once per
+  /// interpreter and set of capabilities) and binds its exports in the
+  /// current scope, optionally restricted to some names and prefixed. The
+  /// module runs without the capabilities denied to the importing scope.
+  pub fn builtin_import(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_at_least_args(1, args.len())?;
+
+    let path: String = (&args[0]).try_into()?;
+    let mut only = None;
+    let mut prefix = None;
+
+    for clause in &args[1..] {
+      let clause: List<S> = clause.try_into()?;
+      let clause: Vec<Value<S>> = clause.into_iter().collect();
+
+      match clause.first() {
+        Some(head) if self.is_symbol_named(head, "only") => {
+          let mut names = Vec::new();
+
+          for name in &clause[1..] {
+            let sym: Sym<S> = name.try_into()?;
+            names.push(sym.as_symbol());
+          }
+
+          only = Some(names);
+        },
+        Some(head) if self.is_symbol_named(head, "prefix") => {
+          assert_exactly_args(2, clause.len())?;
+          let sym: Sym<S> = (&clause[1]).try_into()?;
+          prefix = Some(self.interner.resolve(sym.as_symbol()).unwrap_or("").to_string());
+        },
+        _ => {
+          return Err(RuntimeError::TypeError {
+            expected: "ImportClause".to_string(),
+            got: self.format_value(&Value::List(clause.into_iter().collect())),
+          });
+        },
+      }
+    }
+
+    let module = self.load_module(&env, &path)?;
+
+    let names = match only {
+      Some(names) => {
+        for name in &names {
+          if !module.exports.contains(name) {
+            return Err(RuntimeError::ImportError {
+              detail: format!(
+                "module \"{}\" does not export {}",
+                path,
+                self.interner.resolve(*name).unwrap_or("<>"),
+              ),
+            });
+          }
+        }
+
+        names
+      },
+      None => module.exports.clone(),
+    };
+
+    for name in names {
+      let val = module.env.borrow().get(name).ok_or_else(|| RuntimeError::UndefinedSymbol {
+        detail: self.interner.resolve(name).unwrap_or("<>").to_string(),
+      })?;
+
+      let local = match &prefix {
+        Some(prefix) => {
+          let local = format!("{}{}", prefix, self.interner.resolve(name).unwrap_or("<>"));
+          self.interner.get_or_intern(local)
+        },
+        None => name,
+      };
+
+      env.borrow_mut().define(local, val);
+    }
+
+    Ok(Value::default())
+  }
+
+  /// `(load "path")` evaluates a file in the current scope, yielding its
+  /// last value. Relative paths are resolved from the directory of the file
+  /// being evaluated, or the working directory.
+  pub fn builtin_load(
+    &mut self,
+    env: Rc<RefCell<Env<S>>>,
+    args: Vec<Value<S>>,
+  ) -> Result<Value<S>> {
+    assert_exactly_args(1, args.len())?;
+
+    let path: String = self.eval_expression(env.clone(), args[0].clone())?.try_into()?;
+    let path = self.current_dir().join(path);
+    self.eval_file(env, path)
+  }
+
+  fn current_dir(&self) -> PathBuf {
+    self.current_file
+      .as_ref()
+      .and_then(|file| file.parent())
+      .map(Path::to_path_buf)
+      .unwrap_or_default()
+  }
+
+  /// Find a module relative to the importing file (or the working directory),
+  /// then in the search paths. The `.lisp` extension may be omitted.
+  fn resolve_module(&self, path: &str) -> Result<PathBuf> {
+    let mut candidates = vec![PathBuf::from(path)];
+
+    if Path::new(path).extension().is_none() {
+      candidates.push(PathBuf::from(format!("{}.lisp", path)));
+    }
+
+    let importing_dir = self.current_dir();
+    let dirs = std::iter::once(&importing_dir).chain(self.search_paths.iter());
+
+    for dir in dirs {
+      for candidate in &candidates {
+        let full = dir.join(candidate);
+
+        if full.is_file() {
+          return Ok(full.canonicalize()?);
+        }
+      }
+    }
+
+    Err(RuntimeError::ImportError {
+      detail: format!("module \"{}\" not found", path),
+    })
+  }
+
+  fn load_module(&mut self, importer: &Rc<RefCell<Env<S>>>, path: &str) -> Result<Rc<Module<S>>> {
+    let resolved = self.resolve_module(path)?;
+    let denied: Vec<Capability> = Capability::ALL
+      .into_iter()
+      .filter(|capability| !importer.borrow().allows(*capability))
+      .collect();
+
+    let key = (resolved.clone(), denied.clone());
+
+    if let Some(module) = self.modules.get(&key) {
+      return Ok(module.clone());
+    }
+
+    if let Some(index) = self.loading.iter().position(|frame| frame.path == resolved) {
+      let chain = self.loading[index..]
+        .iter()
+        .map(|frame| frame.path.display().to_string())
+        .chain(std::iter::once(resolved.display().to_string()))
+        .collect::<Vec<String>>()
+        .join(" -> ");
+
+      return Err(RuntimeError::ImportError {
+        detail: format!("cyclic import: {}", chain),
+      });
+    }
+
+    let builder = denied
+      .into_iter()
+      .fold(EnvBuilder::new(), |builder, capability| builder.without(capability));
+    let env = Rc::new(RefCell::new(Env::extend(builder.build(self))));
+
+    self.loading.push(ModuleFrame { path: resolved.clone(), exports: None });
+    let result = self.eval_file(env.clone(), &resolved);
+    let frame = self.loading.pop();
+    result?;
+
+    let exports = frame
+      .and_then(|frame| frame.exports)
+      .unwrap_or_else(|| env.borrow().symbols());
+
+    let module = Rc::new(Module { env, exports });
+    self.modules.insert(key, module.clone());
+    Ok(module)
+  }
+}
@@ -0,0 +1,203 @@
+use std::{rc::Rc, cell::RefCell, collections::HashMap};
+use lispers_common::{Backend, Symbol};
+
+use crate::prelude::*;
+use crate::data::Value;
+use crate::env::Env;
+use super::Interpreter;
+
+type Bindings<S> = Vec<(S, Value<S>)>;
+
+// Patterns are unevaluated forms:
+//
+//  - `name` binds the whole value, `_` ignores it;
+//  - booleans, numbers, characters, strings and keywords match values equal
+//    to themselves, `(quote x)` matches the quoted form (usually a symbol);
+//  - `(a b)` or `[a b]` matches a list or vector of exactly two elements;
+//  - `(a b . rest)` or `(a b &rest rest)` binds the remaining elements as a
+//    list;
+//  - `{a :a b "b"}` binds `a` and `b` to the values found under the given
+//    keys of a map, `{:keys [a b]}` is a shorthand for `{a :a b :b}`. Missing
+//    keys bind nil.
+
+impl<S: Symbol, B: Backend<S>> Interpreter<S, B> {
+  /// Bind every name in `pattern` to the matching part of `value` in `scope`.
+  pub(super) fn bind_pattern(
+    &self,
+    scope: &Rc<RefCell<Env<S>>>,
+    pattern: &Value<S>,
+    value: Value<S>,
+  ) -> Result<()> {
+    if self.match_pattern(scope, pattern, &value)? {
+      Ok(())
+    }
+    else {
+      Err(RuntimeError::PatternMismatch {
+        pattern: self.format_value(pattern),
+        value: self.format_value(&value),
+      })
+    }
+  }
+
+  /// Like `bind_pattern`, but a mismatch returns `false` and binds nothing.
+  pub(super) fn match_pattern(
+    &self,
+    scope: &Rc<RefCell<Env<S>>>,
+    pattern: &Value<S>,
+    value: &Value<S>,
+  ) -> Result<bool> {
+    let mut bindings = Vec::new();
+
+    if !self.destructure(pattern, value, &mut bindings)? {
+      return Ok(false);
+    }
+
+    let mut scope = scope.borrow_mut();
+
+    for (sym, val) in bindings {
+      scope.define(sym, val);
+    }
+
+    Ok(true)
+  }
+
+  fn invalid_pattern(&self, pattern: &Value<S>) -> RuntimeError {
+    RuntimeError::TypeError {
+      expected: "Pattern".to_string(),
+      got: self.format_value(pattern),
+    }
+  }
+
+  /// Collect the bindings of `pattern` against `value`, returning `false`
+  /// when the value does not have the expected shape.
+  fn destructure(
+    &self,
+    pattern: &Value<S>,
+    value: &Value<S>,
+    bindings: &mut Bindings<S>,
+  ) -> Result<bool> {
+    match pattern {
+      Value::Symbol(sym) => {
+        if !self.is_symbol_named(pattern, "_") {
+          bindings.push((sym.as_symbol(), value.clone()));
+        }
+
+        Ok(true)
+      },
+      Value::Boolean(..)
+      | Value::Integer(..)
+      | Value::Float(..)
+      | Value::Char(..)
+      | Value::String(..)
+      | Value::Keyword(..) => {
+        Ok(pattern == value)
+      },
+      Value::List(list) => {
+        let parts: Vec<Value<S>> = list.into_iter().collect();
+
+        match parts.as_slice() {
+          [quote, quoted] if self.is_symbol_named(quote, "quote") => Ok(quoted == value),
+          _ => self.destructure_sequence(&parts, value, bindings),
+        }
+      },
+      Value::Vector(parts) => {
+        let parts = parts.borrow().clone();
+        self.destructure_sequence(&parts, value, bindings)
+      },
+      Value::Map(entries) => {
+        self.destructure_map(entries, value, bindings)
+      },
+      _ => Err(self.invalid_pattern(pattern)),
+    }
+  }
+
+  fn destructure_sequence(
+    &self,
+    parts: &[Value<S>],
+    value: &Value<S>,
+    bindings: &mut Bindings<S>,
+  ) -> Result<bool> {
+    let items: Vec<Value<S>> = match value {
+      Value::List(list) => list.into_iter().collect(),
+      Value::Vector(items) => items.borrow().clone(),
+      Value::PersistentVector(items) => items.iter().cloned().collect(),
+      _ => return Ok(false),
+    };
+
+    let rest_marker = parts.iter().position(|part| {
+      self.is_symbol_named(part, ".") || self.is_symbol_named(part, "&rest")
+    });
+
+    let (fixed, rest) = match rest_marker {
+      Some(index) if index + 2 == parts.len() => (&parts[..index], Some(&parts[index + 1])),
+      Some(index) => return Err(self.invalid_pattern(&parts[index])),
+      None => (parts, None),
+    };
+
+    if items.len() < fixed.len() || (rest.is_none() && items.len() != fixed.len()) {
+      return Ok(false);
+    }
+
+    for (part, item) in std::iter::zip(fixed, &items) {
+      if !self.destructure(part, item, bindings)? {
+        return Ok(false);
+      }
+    }
+
+    match rest {
+      Some(rest) => {
+        let tail = Value::List(items[fixed.len()..].iter().cloned().collect());
+        self.destructure(rest, &tail, bindings)
+      },
+      None => Ok(true),
+    }
+  }
+
+  fn destructure_map(
+    &self,
+    entries: &HashMap<Value<S>, Value<S>>,
+    value: &Value<S>,
+    bindings: &mut Bindings<S>,
+  ) -> Result<bool> {
+    let lookup = |key: &Value<S>| -> Value<S> {
+      let found = match value {
+        Value::Map(map) => map.get(key).cloned(),
+        Value::PersistentMap(map) => map.get(key).cloned(),
+        _ => None,
+      };
+
+      found.unwrap_or_default()
+    };
+
+    if !matches!(value, Value::Map(..) | Value::PersistentMap(..)) {
+      return Ok(false);
+    }
+
+    for (part, key) in entries.iter() {
+      let is_keys = matches!(
+        part,
+        Value::Keyword(sym) if self.interner.resolve(sym.as_symbol()) == Some("keys")
+      );
+
+      if is_keys {
+        let names: Vec<Value<S>> = match key {
+          Value::Vector(names) => names.borrow().clone(),
+          Value::List(names) => names.into_iter().collect(),
+          _ => return Err(self.invalid_pattern(key)),
+        };
+
+        for name in names {
+          match name {
+            Value::Symbol(sym) => bindings.push((sym.as_symbol(), lookup(&Value::Keyword(sym)))),
+            _ => return Err(self.invalid_pattern(&name)),
+          }
+        }
+      }
+      else if !self.destructure(part, &lookup(key), bindings)? {
+        return Ok(false);
+      }
+    }
+
+    Ok(true)
+  }
+}
@@ -4,7 +4,14 @@ mod data;
 mod env;
 mod interpreter;
 
+#[cfg(feature = "serde")]
+pub mod serialization;
+#[cfg(feature = "serde")]
+pub mod config;
+
 pub use self::{
   prelude::RuntimeError,
   interpreter::Interpreter,
+  env::{EnvBuilder, Capability},
+  data::{Value, PersistentVector, PersistentMap},
 };
@@ -8,9 +8,27 @@ pub enum RuntimeError {
   SyntaxError(SyntaxError),
   NilValue { detail: String },
   UndefinedSymbol { detail: String },
-  TooFewArguments { expected: usize, got: usize },
-  TooManyArguments { expected: usize, got: usize },
+  TooFewArguments { min: usize, max: Option<usize>, got: usize },
+  TooManyArguments { min: usize, max: Option<usize>, got: usize },
   TypeError { expected: String, got: String },
+  IndexOutOfBounds { index: i64, length: usize },
+  PatternMismatch { pattern: String, value: String },
+  MatchError { value: String },
+  Break,
+  Continue,
+  LoopError { detail: String },
+  ImportError { detail: String },
+  FormatError { detail: String },
+  JsonError { detail: String },
+  PermissionDenied { detail: String },
+}
+
+fn arity_range(min: usize, max: Option<usize>) -> String {
+  match max {
+    Some(max) if max == min => min.to_string(),
+    Some(max) => format!("{} to {}", min, max),
+    None => format!("at least {}", min),
+  }
 }
 
 impl std::fmt::Display for RuntimeError {
@@ -28,25 +46,60 @@ impl std::fmt::Display for RuntimeError {
       Self::UndefinedSymbol { detail }=> {
         write!(f, "UndefinedSymbol: {}", detail)
       },
-      Self::TooFewArguments { expected, got } => {
+      Self::TooFewArguments { min, max, got } => {
         write!(
           f,
           "ArityError: Too few arguments for function, expected {} but got {}",
-          expected,
+          arity_range(*min, *max),
           got,
         )
       },
-      Self::TooManyArguments { expected, got } => {
+      Self::TooManyArguments { min, max, got } => {
         write!(
           f,
           "ArityError: Too many arguments for function, expected {} but got {}",
-          expected,
+          arity_range(*min, *max),
           got,
         )
       },
       Self::TypeError { expected, got } => {
         write!(f, "TypeError: expected <{}> but got <{}>", expected, got)
-      }
+      },
+      Self::IndexOutOfBounds { index, length } => {
+        write!(
+          f,
+          "IndexError: index {} out of bounds for length {}",
+          index,
+          length,
+        )
+      },
+      Self::PatternMismatch { pattern, value } => {
+        write!(f, "PatternError: {} does not match pattern {}", value, pattern)
+      },
+      Self::MatchError { value } => {
+        write!(f, "MatchError: no clause matches {}", value)
+      },
+      Self::Break => {
+        write!(f, "LoopError: break outside of a loop")
+      },
+      Self::Continue => {
+        write!(f, "LoopError: continue outside of a loop")
+      },
+      Self::LoopError { detail } => {
+        write!(f, "LoopError: {}", detail)
+      },
+      Self::ImportError { detail } => {
+        write!(f, "ImportError: {}", detail)
+      },
+      Self::FormatError { detail } => {
+        write!(f, "FormatError: {}", detail)
+      },
+      Self::JsonError { detail } => {
+        write!(f, "JsonError: {}", detail)
+      },
+      Self::PermissionDenied { detail } => {
+        write!(f, "PermissionDenied: {}", detail)
+      },
     }
   }
 }
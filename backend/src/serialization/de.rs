@@ -0,0 +1,356 @@
+use std::{collections::HashMap, rc::Rc, cell::RefCell, fmt, marker::PhantomData};
+use serde::de::{
+  self,
+  Deserialize,
+  Deserializer,
+  DeserializeSeed,
+  Visitor,
+  SeqAccess,
+  MapAccess,
+  EnumAccess,
+  VariantAccess,
+  IntoDeserializer,
+  Unexpected,
+  Error as _,
+};
+use lispers_common::Symbol;
+
+use crate::data::Value;
+use super::{Names, Error, PathSegment};
+
+struct ValueVisitor<S: Symbol>(PhantomData<S>);
+
+impl<'de, S: Symbol> Visitor<'de> for ValueVisitor<S> {
+  type Value = Value<S>;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("any value")
+  }
+
+  fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+    Ok(Value::Boolean(v))
+  }
+
+  fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+    Ok(Value::Integer(v))
+  }
+
+  fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+    match i64::try_from(v) {
+      Ok(v) => Ok(Value::Integer(v)),
+      Err(_) => Ok(Value::Float(v as f64)),
+    }
+  }
+
+  fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+    Ok(Value::Float(v))
+  }
+
+  fn visit_char<E: de::Error>(self, v: char) -> Result<Self::Value, E> {
+    Ok(Value::Char(v))
+  }
+
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+    Ok(Value::String(v.to_string()))
+  }
+
+  fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+    Ok(Value::String(v))
+  }
+
+  fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+    let items = v.iter().map(|byte| Value::Integer(*byte as i64)).collect();
+    Ok(Value::Vector(Rc::new(RefCell::new(items))))
+  }
+
+  fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+    Ok(Value::default())
+  }
+
+  fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+    Ok(Value::default())
+  }
+
+  fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+    Value::deserialize(deserializer)
+  }
+
+  fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+    Value::deserialize(deserializer)
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+    let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+    while let Some(item) = seq.next_element()? {
+      items.push(item);
+    }
+
+    Ok(Value::Vector(Rc::new(RefCell::new(items))))
+  }
+
+  fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+    let mut entries = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+
+    while let Some((key, val)) = map.next_entry::<Value<S>, Value<S>>()? {
+      key.assert_hashable().map_err(A::Error::custom)?;
+      entries.insert(key, val);
+    }
+
+    Ok(Value::Map(Rc::new(entries)))
+  }
+}
+
+/// Sequences become vectors and maps become hash maps with string keys, as
+/// there is no interner to create keywords with.
+impl<'de, S: Symbol> Deserialize<'de> for Value<S> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_any(ValueVisitor(PhantomData))
+  }
+}
+
+/// Reads Rust types out of a value, see `Interpreter::deserializer`.
+pub struct ValueDeserializer<'a, S: Symbol> {
+  value: Value<S>,
+  names: Option<&'a dyn Names<S>>,
+}
+
+impl<'a, S: Symbol> ValueDeserializer<'a, S> {
+  pub(crate) fn new(value: Value<S>, names: Option<&'a dyn Names<S>>) -> Self {
+    Self { value, names }
+  }
+
+  fn nested(&self, value: Value<S>) -> Self {
+    Self { value, names: self.names }
+  }
+
+  fn unexpected(&self) -> Unexpected<'_> {
+    match &self.value {
+      Value::Boolean(val) => Unexpected::Bool(*val),
+      Value::Integer(val) => Unexpected::Signed(*val),
+      Value::Float(val) => Unexpected::Float(*val),
+      Value::Char(val) => Unexpected::Char(*val),
+      Value::String(val) => Unexpected::Str(val),
+      Value::List(list) if list.empty() => Unexpected::Unit,
+      Value::Map(..) | Value::PersistentMap(..) => Unexpected::Map,
+      Value::List(..) | Value::Vector(..) | Value::PersistentVector(..) => Unexpected::Seq,
+      value => Unexpected::Other(value.as_type().name()),
+    }
+  }
+
+  /// Name of a string, symbol or keyword.
+  fn name(&self) -> Option<String> {
+    match &self.value {
+      Value::String(val) => Some(val.clone()),
+      Value::Symbol(sym) | Value::Keyword(sym) => {
+        self.names
+          .and_then(|names| names.name(sym.as_symbol()))
+          .map(str::to_string)
+      },
+      _ => None,
+    }
+  }
+
+  /// Segment naming a map entry by its key.
+  fn key_segment(&self) -> PathSegment {
+    match &self.value {
+      Value::Integer(val) => PathSegment::Key(val.to_string()),
+      _ => PathSegment::Key(self.name().unwrap_or_else(|| "?".to_string())),
+    }
+  }
+
+  fn items(&self) -> Option<Vec<Value<S>>> {
+    match &self.value {
+      Value::List(list) => Some(list.into_iter().collect()),
+      Value::Vector(items) => Some(items.borrow().clone()),
+      Value::PersistentVector(items) => Some(items.iter().cloned().collect()),
+      _ => None,
+    }
+  }
+
+  fn entries(&self) -> Option<Vec<(Value<S>, Value<S>)>> {
+    match &self.value {
+      Value::Map(map) => Some(map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()),
+      Value::PersistentMap(map) => Some(map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()),
+      _ => None,
+    }
+  }
+}
+
+impl<'de, S: Symbol> Deserializer<'de> for ValueDeserializer<'_, S> {
+  type Error = Error;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    match &self.value {
+      Value::Boolean(val) => visitor.visit_bool(*val),
+      Value::Integer(val) => visitor.visit_i64(*val),
+      Value::Float(val) => visitor.visit_f64(*val),
+      Value::Char(val) => visitor.visit_char(*val),
+      Value::String(val) => visitor.visit_str(val),
+      Value::Symbol(..) | Value::Keyword(..) => match self.name() {
+        Some(name) => visitor.visit_string(name),
+        None => Err(Error::custom("cannot name a symbol without its interpreter")),
+      },
+      Value::List(list) if list.empty() => visitor.visit_unit(),
+      Value::List(..) | Value::Vector(..) | Value::PersistentVector(..) => {
+        let items = self.items().unwrap_or_default();
+        visitor.visit_seq(Seq { items: items.into_iter().enumerate(), de: &self })
+      },
+      Value::Map(..) | Value::PersistentMap(..) => {
+        let entries = self.entries().unwrap_or_default();
+        visitor.visit_map(Map { entries: entries.into_iter(), val: None, de: &self })
+      },
+      Value::Function(..) | Value::Port(..) => {
+        Err(Error::invalid_type(self.unexpected(), &visitor))
+      },
+    }
+  }
+
+  fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    match &self.value {
+      Value::List(list) if list.empty() => visitor.visit_none(),
+      _ => visitor.visit_some(self),
+    }
+  }
+
+  fn deserialize_newtype_struct<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  /// Unit variants are written as a string, symbol or keyword, other
+  /// variants as a map with a single entry from the variant to its content.
+  fn deserialize_enum<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    _variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    if let Some(name) = self.name() {
+      return visitor.visit_enum(name.into_deserializer());
+    }
+
+    match self.entries().as_deref() {
+      Some([(key, val)]) => {
+        let key = self.nested(key.clone());
+        let segment = key.key_segment();
+
+        visitor
+          .visit_enum(Variant { key, val: self.nested(val.clone()) })
+          .map_err(|err| err.at(segment))
+      },
+      _ => Err(Error::invalid_type(self.unexpected(), &"enum variant")),
+    }
+  }
+
+  /// Skipped values are not inspected, so unknown fields may hold
+  /// functions, such as helpers defined in a configuration file.
+  fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    visitor.visit_unit()
+  }
+
+  serde::forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+    identifier
+  }
+}
+
+struct Seq<'a, 'n, S: Symbol> {
+  items: std::iter::Enumerate<std::vec::IntoIter<Value<S>>>,
+  de: &'a ValueDeserializer<'n, S>,
+}
+
+impl<'de, S: Symbol> SeqAccess<'de> for Seq<'_, '_, S> {
+  type Error = Error;
+
+  fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Error> {
+    match self.items.next() {
+      Some((index, item)) => seed
+        .deserialize(self.de.nested(item))
+        .map(Some)
+        .map_err(|err| err.at(PathSegment::Index(index))),
+      None => Ok(None),
+    }
+  }
+
+  fn size_hint(&self) -> Option<usize> {
+    Some(self.items.len())
+  }
+}
+
+struct Map<'a, 'n, S: Symbol> {
+  entries: std::vec::IntoIter<(Value<S>, Value<S>)>,
+  val: Option<(PathSegment, Value<S>)>,
+  de: &'a ValueDeserializer<'n, S>,
+}
+
+impl<'de, S: Symbol> MapAccess<'de> for Map<'_, '_, S> {
+  type Error = Error;
+
+  fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+    match self.entries.next() {
+      Some((key, val)) => {
+        let key = self.de.nested(key);
+        let segment = key.key_segment();
+        self.val = Some((segment.clone(), val));
+        seed.deserialize(key).map(Some).map_err(|err| err.at(segment))
+      },
+      None => Ok(None),
+    }
+  }
+
+  fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+    let (segment, val) = self.val
+      .take()
+      .ok_or_else(|| Error::custom("value requested before key"))?;
+
+    seed.deserialize(self.de.nested(val)).map_err(|err| err.at(segment))
+  }
+
+  fn size_hint(&self) -> Option<usize> {
+    Some(self.entries.len())
+  }
+}
+
+struct Variant<'n, S: Symbol> {
+  key: ValueDeserializer<'n, S>,
+  val: ValueDeserializer<'n, S>,
+}
+
+impl<'de, 'n, S: Symbol> EnumAccess<'de> for Variant<'n, S> {
+  type Error = Error;
+  type Variant = ValueDeserializer<'n, S>;
+
+  fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant), Error> {
+    let variant = seed.deserialize(self.key)?;
+    Ok((variant, self.val))
+  }
+}
+
+impl<'de, S: Symbol> VariantAccess<'de> for ValueDeserializer<'_, S> {
+  type Error = Error;
+
+  fn unit_variant(self) -> Result<(), Error> {
+    Deserialize::deserialize(self)
+  }
+
+  fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+    seed.deserialize(self)
+  }
+
+  fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+    self.deserialize_any(visitor)
+  }
+
+  fn struct_variant<V: Visitor<'de>>(
+    self,
+    _fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    self.deserialize_any(visitor)
+  }
+}
@@ -0,0 +1,96 @@
+//! Conversions between Lisp values and Rust types through `serde`.
+//!
+//! `Value` implements `Serialize` and `Deserialize` on its own, but symbols
+//! and keywords are interned and can only be named with the interpreter
+//! that created them: use `Interpreter::serializable` and
+//! `Interpreter::from_value` (or `Interpreter::deserializer`) for values
+//! containing them, such as maps with keyword keys.
+//!
+//! Lists and vectors map to sequences, nil to unit, maps to maps, keywords
+//! and symbols to their name. Deserializing a `Value` yields vectors and
+//! maps with string keys.
+
+use lispers_common::{StringInterner, Backend, Symbol};
+
+mod ser;
+mod de;
+
+pub use self::{
+  ser::Serializable,
+  de::ValueDeserializer,
+};
+
+/// Resolves interned symbols to their name.
+pub(crate) trait Names<S: Symbol> {
+  fn name(&self, sym: S) -> Option<&str>;
+}
+
+impl<S: Symbol, B: Backend<S>> Names<S> for StringInterner<B> {
+  fn name(&self, sym: S) -> Option<&str> {
+    self.resolve(sym)
+  }
+}
+
+/// Step from a sequence or map to one of its items.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+  Index(usize),
+  Key(String),
+}
+
+#[derive(Debug)]
+pub struct Error {
+  message: String,
+  path: Vec<PathSegment>,
+}
+
+impl Error {
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// Location of the offending value inside the deserialized one, empty
+  /// when the error is about the value itself.
+  pub fn path(&self) -> &[PathSegment] {
+    &self.path
+  }
+
+  pub(crate) fn at(mut self, segment: PathSegment) -> Self {
+    self.path.insert(0, segment);
+    self
+  }
+}
+
+impl std::fmt::Display for Error {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "SerdeError: {}", self.message)?;
+
+    if !self.path.is_empty() {
+      write!(f, " at ")?;
+
+      for (index, segment) in self.path.iter().enumerate() {
+        match segment {
+          PathSegment::Index(i) => write!(f, "[{}]", i)?,
+          PathSegment::Key(key) if index == 0 => write!(f, "{}", key)?,
+          PathSegment::Key(key) => write!(f, ".{}", key)?,
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl std::error::Error for Error {}
+
+impl serde::ser::Error for Error {
+  fn custom<T: std::fmt::Display>(msg: T) -> Self {
+    Self { message: msg.to_string(), path: Vec::new() }
+  }
+}
+
+impl serde::de::Error for Error {
+  fn custom<T: std::fmt::Display>(msg: T) -> Self {
+    Self { message: msg.to_string(), path: Vec::new() }
+  }
+}
@@ -0,0 +1,101 @@
+use serde::ser::{Serialize, Serializer, SerializeSeq, SerializeMap, Error as _};
+use lispers_common::Symbol;
+
+use crate::data::{Value, Sym};
+use super::Names;
+
+/// A value paired with the names of its symbols, see
+/// `Interpreter::serializable`.
+pub struct Serializable<'a, S: Symbol> {
+  value: &'a Value<S>,
+  names: Option<&'a dyn Names<S>>,
+}
+
+impl<'a, S: Symbol> Serializable<'a, S> {
+  pub(crate) fn new(value: &'a Value<S>, names: Option<&'a dyn Names<S>>) -> Self {
+    Self { value, names }
+  }
+
+  fn nested(&self, value: &'a Value<S>) -> Self {
+    Self { value, names: self.names }
+  }
+
+  fn name<E: serde::ser::Error>(&self, sym: &Sym<S>) -> Result<&'a str, E> {
+    self.names
+      .and_then(|names| names.name(sym.as_symbol()))
+      .ok_or_else(|| E::custom("cannot name a symbol without its interpreter"))
+  }
+}
+
+impl<S: Symbol> Serialize for Serializable<'_, S> {
+  fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+    match self.value {
+      Value::Boolean(val) => serializer.serialize_bool(*val),
+      Value::Integer(val) => serializer.serialize_i64(*val),
+      Value::Float(val) => serializer.serialize_f64(*val),
+      Value::Char(val) => serializer.serialize_char(*val),
+      Value::String(val) => serializer.serialize_str(val),
+      Value::Symbol(sym) | Value::Keyword(sym) => serializer.serialize_str(self.name(sym)?),
+      Value::List(list) if list.empty() => serializer.serialize_unit(),
+      Value::List(list) => {
+        let items: Vec<Value<S>> = list.into_iter().collect();
+        let mut seq = serializer.serialize_seq(Some(items.len()))?;
+
+        for item in &items {
+          seq.serialize_element(&self.nested(item))?;
+        }
+
+        seq.end()
+      },
+      Value::Vector(items) => {
+        let items = items.borrow();
+        let mut seq = serializer.serialize_seq(Some(items.len()))?;
+
+        for item in items.iter() {
+          seq.serialize_element(&self.nested(item))?;
+        }
+
+        seq.end()
+      },
+      Value::PersistentVector(items) => {
+        let mut seq = serializer.serialize_seq(Some(items.len()))?;
+
+        for item in items.iter() {
+          seq.serialize_element(&self.nested(item))?;
+        }
+
+        seq.end()
+      },
+      Value::Map(entries) => {
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+
+        for (key, val) in entries.iter() {
+          map.serialize_entry(&self.nested(key), &self.nested(val))?;
+        }
+
+        map.end()
+      },
+      Value::PersistentMap(entries) => {
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+
+        for (key, val) in entries.iter() {
+          map.serialize_entry(&self.nested(key), &self.nested(val))?;
+        }
+
+        map.end()
+      },
+      Value::Function(..) | Value::Port(..) => Err(R::Error::custom(format!(
+        "cannot serialize a value of type {}",
+        self.value.as_type().name(),
+      ))),
+    }
+  }
+}
+
+/// Symbols and keywords cannot be named without their interpreter and fail
+/// to serialize, see `Interpreter::serializable`.
+impl<S: Symbol> Serialize for Value<S> {
+  fn serialize<R: Serializer>(&self, serializer: R) -> Result<R::Ok, R::Error> {
+    Serializable::new(self, None).serialize(serializer)
+  }
+}
@@ -1,22 +1,57 @@
+use std::{rc::Rc, cell::RefCell};
+use lispers_common::Symbol;
+
 use crate::prelude::*;
+use crate::env::{Env, Capability};
 
 pub fn assert_exactly_args(expected: usize, got: usize) -> Result<()> {
-  if got < expected {
-    Err(RuntimeError::TooFewArguments { expected, got })
+  assert_args_between(expected, Some(expected), got)
+}
+
+pub fn assert_at_least_args(expected: usize, got: usize) -> Result<()> {
+  assert_args_between(expected, None, got)
+}
+
+/// Check that `got` lies within `min..=max`, with no upper bound when `max`
+/// is `None`.
+pub fn assert_args_between(min: usize, max: Option<usize>, got: usize) -> Result<()> {
+  if got < min {
+    Err(RuntimeError::TooFewArguments { min, max, got })
   }
-  else if got > expected {
-    Err(RuntimeError::TooManyArguments { expected, got })
+  else if max.is_some_and(|max| got > max) {
+    Err(RuntimeError::TooManyArguments { min, max, got })
   }
   else {
     Ok(())
   }
 }
 
-pub fn assert_at_least_args(expected: usize, got: usize) -> Result<()> {
-  if got < expected {
-    Err(RuntimeError::TooFewArguments { expected, got })
+/// Check that `got` trailing arguments form key/value pairs.
+pub fn assert_pairs(got: usize) -> Result<()> {
+  if got.is_multiple_of(2) {
+    Ok(())
   }
   else {
+    Err(RuntimeError::TypeError {
+      expected: "KeyValuePairs".to_string(),
+      got: format!("an odd number of key/value arguments ({})", got),
+    })
+  }
+}
+
+/// Check that `env` allows `capability`, for builtins that only need it in
+/// some cases, such as `format` to the current output port.
+pub fn assert_capability<S: Symbol>(
+  env: &Rc<RefCell<Env<S>>>,
+  capability: Capability,
+  detail: &str,
+) -> Result<()> {
+  if env.borrow().allows(capability) {
     Ok(())
   }
+  else {
+    Err(RuntimeError::PermissionDenied {
+      detail: format!("{} requires the {} capability", detail, capability),
+    })
+  }
 }
@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+use lispers_common::{backend::DefaultBackend, symbol::SymbolUsize};
+use lispers_backend::{Interpreter, EnvBuilder, Capability, RuntimeError};
+
+type Symbol = SymbolUsize;
+type Backend = DefaultBackend<Symbol>;
+
+fn eval(builder: EnvBuilder, input: &str) -> Result<String, RuntimeError> {
+  let mut interpreter: Interpreter<Symbol, Backend> = Interpreter::new();
+  let env = builder.build(&mut interpreter);
+  let value = interpreter.eval_string(env, input)?;
+  Ok(interpreter.format_value(&value))
+}
+
+fn fixtures() -> String {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/import").display().to_string()
+}
+
+fn assert_denied(builder: EnvBuilder, input: &str, expected: &str) {
+  match eval(builder, input) {
+    Err(err @ RuntimeError::PermissionDenied { .. }) => {
+      assert_eq!(err.to_string(), format!("PermissionDenied: {}", expected));
+    },
+    other => panic!("{} should be denied, got {:?}", input, other),
+  }
+}
+
+#[test]
+fn restricted() {
+  let builder = EnvBuilder::restricted;
+
+  assert_eq!(eval(builder(), "(reverse (list 1 2))").unwrap(), "(2 1)");
+  assert_eq!(eval(builder(), "(string-upcase (format \"~a\" :x))").unwrap(), ":X");
+
+  assert_denied(builder(), "(exit 1)", "exit requires the process capability");
+  assert_denied(builder(), "(+ 1 2)", "+ requires the arithmetic capability");
+  assert_denied(builder(), "(inc 1)", "+ requires the arithmetic capability");
+  assert_denied(builder(), "(println 1)", "println requires the io capability");
+  assert_denied(builder(), "(read-line)", "read-line requires the io capability");
+  assert_denied(builder(), "(format true \"x\")", "format to the current output port requires the io capability");
+  assert_denied(builder(), "(open-input-file \"x\")", "open-input-file requires the fs capability");
+  assert_denied(builder(), "(load \"x.lisp\")", "load requires the fs capability");
+  assert_denied(builder(), "(import \"x\")", "import requires the fs capability");
+  assert_denied(builder(), "(current-time)", "current-time requires the time capability");
+  assert_denied(builder(), "(map exit (list 1))", "exit requires the process capability");
+}
+
+#[test]
+fn selected_capabilities() {
+  let builder = || EnvBuilder::restricted().with(Capability::Arithmetic).with(Capability::Time);
+
+  assert_eq!(eval(builder(), "(+ 1 2)").unwrap(), "3");
+  assert_eq!(eval(builder(), "(float? (current-time))").unwrap(), "true");
+  assert_denied(builder(), "(exit 1)", "exit requires the process capability");
+
+  let builder = || EnvBuilder::new().without(Capability::Process);
+  assert_eq!(eval(builder(), "(with-output-to-string (lambda () (println 1)))").unwrap(), "1\n");
+  assert_denied(builder(), "(exit 1)", "exit requires the process capability");
+}
+
+#[test]
+fn shadowing() {
+  let builder = EnvBuilder::restricted;
+
+  assert_eq!(eval(builder(), "(def exit (lambda (code) code)) (exit 2)").unwrap(), "2");
+  assert_eq!(eval(builder(), "(let ((+ list)) (+ 1 2))").unwrap(), "(1 2)");
+
+  match eval(builder(), "(undefined-function 1)") {
+    Err(RuntimeError::UndefinedSymbol { .. }) => {},
+    other => panic!("expected an undefined symbol, got {:?}", other),
+  }
+}
+
+#[test]
+fn without_prelude() {
+  let builder = || EnvBuilder::new().prelude(false);
+
+  assert_eq!(eval(builder(), "(+ 1 2)").unwrap(), "3");
+  assert!(matches!(eval(builder(), "(inc 1)"), Err(RuntimeError::UndefinedSymbol { .. })));
+}
+
+#[test]
+fn imported_modules_inherit_capabilities() {
+  let builder = || EnvBuilder::restricted().with(Capability::Fs);
+  let dir = fixtures();
+
+  assert_denied(
+    builder(),
+    &format!("(import \"{}/escape\") (run)", dir),
+    "println requires the io capability",
+  );
+  assert_denied(
+    builder().with(Capability::Io),
+    &format!("(import \"{}/escape\") (with-output-to-string run)", dir),
+    "exit requires the process capability",
+  );
+  assert_denied(
+    builder(),
+    &format!("(import \"{}/escape_at_load\")", dir),
+    "println requires the io capability",
+  );
+}
+
+#[test]
+fn module_cache_is_per_capabilities() {
+  let mut interpreter: Interpreter<Symbol, Backend> = Interpreter::new();
+  let import = format!("(import \"{}/escape\")", fixtures());
+
+  let env = interpreter.default_env();
+  interpreter.eval_string(env, &import).unwrap();
+
+  let env = EnvBuilder::restricted().with(Capability::Fs).build(&mut interpreter);
+  let result = interpreter.eval_string(env, &format!("{} (run)", import));
+  assert!(matches!(result, Err(RuntimeError::PermissionDenied { .. })));
+}
@@ -0,0 +1,19 @@
+use lispers_common::{backend::DefaultBackend, symbol::SymbolUsize};
+use lispers_backend::{Interpreter, RuntimeError};
+
+type Symbol = SymbolUsize;
+type Backend = DefaultBackend<Symbol>;
+
+pub fn eval(input: &str) -> Result<String, RuntimeError> {
+  let mut interpreter: Interpreter<Symbol, Backend> = Interpreter::new();
+  let env = interpreter.default_env();
+  let value = interpreter.eval_string(env, input)?;
+  Ok(interpreter.format_value(&value))
+}
+
+pub fn assert_eval(input: &str, expected: &str) {
+  match eval(input) {
+    Ok(output) => assert_eq!(output, expected, "while evaluating {}", input),
+    Err(err) => panic!("while evaluating {}: {}", input, err),
+  }
+}
@@ -0,0 +1,130 @@
+#![cfg(feature = "serde")]
+
+use serde::Deserialize;
+use lispers_backend::{config, RuntimeError};
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Server {
+  host: String,
+  port: u16,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+  name: String,
+  servers: Vec<Server>,
+  retries: Option<u8>,
+}
+
+#[test]
+fn final_value() {
+  let config: Config = config::from_str(r#"
+    ; computed values are allowed
+    (let ((base 8000))
+      {:name "app"
+       :servers [{:host "a" :port (+ base 1)}
+                 {:host "b" :port (+ base 2)}]
+       :retries ()})
+  "#).unwrap();
+
+  assert_eq!(config, Config {
+    name: "app".to_string(),
+    servers: vec![
+      Server { host: "a".to_string(), port: 8001 },
+      Server { host: "b".to_string(), port: 8002 },
+    ],
+    retries: None,
+  });
+}
+
+#[test]
+fn definitions() {
+  let config: Config = config::from_str(r#"
+    (def port 80)
+    (def server (lambda (host) {:host host :port port}))
+    (def name "app")
+    (def servers (map server (list "a" "b")))
+    (def retries 3)
+  "#).unwrap();
+
+  assert_eq!(config, Config {
+    name: "app".to_string(),
+    servers: vec![
+      Server { host: "a".to_string(), port: 80 },
+      Server { host: "b".to_string(), port: 80 },
+    ],
+    retries: Some(3),
+  });
+}
+
+#[test]
+fn from_file() {
+  let path = std::env::temp_dir().join("lispers-config-test.lisp");
+  std::fs::write(&path, "{:host \"h\"\n :port -1}").unwrap();
+
+  let err = config::from_file::<Server, _>(&path).unwrap_err();
+  let location = err.location().unwrap();
+  assert_eq!((location.line, location.column), (2, 8));
+  assert_eq!(location.filename.as_deref(), Some(path.as_path()));
+
+  std::fs::remove_file(&path).unwrap();
+}
+
+fn error_location(input: &str) -> (usize, usize) {
+  let err = config::from_str::<Config>(input).unwrap_err();
+  let location = err.location().unwrap_or_else(|| panic!("{} has no location", err));
+  (location.line, location.column)
+}
+
+#[test]
+fn deserialize_error_locations() {
+  let input = "{:name \"app\"\n :servers [{:host \"a\" :port 1}\n           {:host \"b\" :port \"x\"}]}";
+  assert_eq!(error_location(input), (3, 29));
+
+  let input = "(def name \"app\")\n(def servers (list (hash-map :host 1 :port 2)))";
+  assert_eq!(error_location(input), (2, 36));
+
+  let input = "(def name \"app\")\n(def servers (quote ({:host \"a\" :port 70000})))";
+  assert_eq!(error_location(input), (2, 39));
+
+  let input = "(def name 1)\n(def servers [])";
+  assert_eq!(error_location(input), (1, 11));
+
+  let input = "{:name \"app\"\n :servers (map identity (list {:host 1 :port 2}))}";
+  assert_eq!(error_location(input), (2, 11));
+
+  let err = config::from_str::<Config>("(def name \"app\")").unwrap_err();
+  assert!(err.to_string().contains("missing field `servers`"), "{}", err);
+}
+
+#[test]
+fn eval_error_locations() {
+  let err = config::from_str::<Config>("(def name \"a\")\n\n  (undefined-function 1)").unwrap_err();
+  assert_eq!(err.to_string(), "<>[3;3] UndefinedSymbol: undefined-function");
+
+  let err = config::from_str::<Config>("(def name").unwrap_err();
+  assert!(err.to_string().starts_with("SyntaxError: <>[1;"), "{}", err);
+}
+
+#[test]
+fn sandbox() {
+  for input in [
+    "(println 1)",
+    "(load \"other.lisp\")",
+    "(import \"other\")",
+    "(exit 1)",
+    "(open-output-file \"/tmp/lispers-sandbox\")",
+    "(read-line)",
+    "(read)",
+    "(write-string \"x\")",
+    "(format true \"x\")",
+  ] {
+    match config::from_str::<()>(input) {
+      Err(config::ConfigError::Eval { error: RuntimeError::PermissionDenied { .. }, .. }) => {},
+      other => panic!("{} should be denied, got {:?}", input, other),
+    }
+  }
+
+  let value: String = config::from_str("(format \"~a-~a\" 1 (read \"(2)\"))").unwrap();
+  assert_eq!(value, "1-(2)");
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn truthiness() {
+  assert_eval("(if false 1 2)", "2");
+  assert_eval("(if () 1 2)", "2");
+  assert_eval("(if 0 1 2)", "1");
+  assert_eval("(if \"\" 1 2)", "1");
+  assert_eval("(if (list 1) 1 2)", "1");
+  assert_eval("(if [] 1 2)", "1");
+}
+
+#[test]
+fn if_without_else() {
+  assert_eval("(if true 1)", "1");
+  assert_eval("(if false 1)", "()");
+  assert!(matches!(eval("(if true)"), Err(RuntimeError::TooFewArguments { .. })));
+}
+
+#[test]
+fn not() {
+  assert_eval("(not false)", "true");
+  assert_eval("(not ())", "true");
+  assert_eval("(not 0)", "false");
+  assert_eval("(filter not (list 1 false 2 ()))", "(false ())");
+}
+
+#[test]
+fn and() {
+  assert_eval("(and)", "true");
+  assert_eval("(and 1 2 3)", "3");
+  assert_eval("(and 1 false 3)", "false");
+  assert_eval("(and false (car ()))", "false");
+}
+
+#[test]
+fn or() {
+  assert_eval("(or)", "false");
+  assert_eval("(or false () 3)", "3");
+  assert_eval("(or false ())", "()");
+  assert_eval("(or 1 (car ()))", "1");
+}
+
+#[test]
+fn cond() {
+  assert_eval("(cond (false 1) (true 2) (true 3))", "2");
+  assert_eval("(cond (false 1) (else 2 3))", "3");
+  assert_eval("(cond (false 1))", "()");
+  assert_eval("(cond ((car (list 4))))", "4");
+  assert_eval("(cond ((car (list 4)) => (lambda (x) (* x 2))))", "8");
+}
+
+#[test]
+fn case() {
+  assert_eval("(case (+ 1 1) ((1) :one) ((2 3) :few) (else :many))", ":few");
+  assert_eval("(case 7 ((1) :one) (else :many))", ":many");
+  assert_eval("(case (quote b) ((a) 1) ((b) 2))", "2");
+  assert_eval("(case #\\x ((#\\y) 1))", "()");
+}
+
+#[test]
+fn when() {
+  assert_eval("(when true 1 2)", "2");
+  assert_eval("(when false (car ()))", "()");
+}
+
+#[test]
+fn unless() {
+  assert_eval("(unless false 1 2)", "2");
+  assert_eval("(unless true (car ()))", "()");
+}
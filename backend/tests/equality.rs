@@ -0,0 +1,78 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn eq_scalars() {
+  assert_eval("(eq? 1 1)", "true");
+  assert_eval("(eq? 1 2)", "false");
+  assert_eval("(eq? #\\a #\\a)", "true");
+  assert_eval("(eq? true true)", "true");
+  assert_eval("(eq? (quote a) (quote a))", "true");
+  assert_eval("(eq? :a :a)", "true");
+  assert_eval("(eq? () ())", "true");
+  assert_eval("(eq? 1 1.0)", "false");
+}
+
+#[test]
+fn eq_is_reflexive() {
+  assert_eval("(def s \"abc\") (eq? s s)", "true");
+  assert_eval("(def f 1.5) (eq? f f)", "true");
+  assert_eval("(def l (list 1 2)) (eq? l l)", "true");
+  assert_eval("(def v [1]) (eq? v v)", "true");
+  assert_eval("(def m {:a 1}) (eq? m m)", "true");
+  assert_eval("(eq? car car)", "true");
+  assert_eval("(def g (lambda () 1)) (eq? g g)", "true");
+}
+
+#[test]
+fn eq_compares_collections_by_identity() {
+  assert_eval("(eq? (list 1 2) (list 1 2))", "false");
+  assert_eval("(eq? [1] [1])", "false");
+  assert_eval("(eq? {:a 1} {:a 1})", "false");
+  assert_eval("(eq? (lambda () 1) (lambda () 1))", "false");
+  assert_eval("(def l (list 1 2)) (eq? (cdr l) (cdr l))", "true");
+}
+
+#[test]
+fn eqv() {
+  assert_eval("(eqv? \"abc\" \"abc\")", "true");
+  assert_eval("(eqv? \"abc\" \"abd\")", "false");
+  assert_eval("(eqv? 1.5 1.5)", "true");
+  assert_eval("(eqv? 0.0 -0.0)", "false");
+  assert_eval("(eqv? (list 1) (list 1))", "false");
+  assert_eval("(def l (list 1)) (eqv? l l)", "true");
+}
+
+#[test]
+fn equal() {
+  assert_eval("(equal? (list 1 (list 2)) (list 1 (list 2)))", "true");
+  assert_eval("(equal? [1 2] [1 2])", "true");
+  assert_eval("(equal? [1 2] (persistent-vector 1 2))", "true");
+  assert_eval("(equal? {:a [1]} {:a [1]})", "true");
+  assert_eval("(equal? {:a 1} {:a 2})", "false");
+  assert_eval("(equal? 1 1.0)", "false");
+  assert_eval("(equal? 0.0 -0.0)", "true");
+}
+
+#[test]
+fn lengths_are_compared() {
+  assert_eval("(= (quote (1 2)) (quote (1 2 3)))", "false");
+  assert_eval("(= (quote (1 2 3)) (quote (1 2)))", "false");
+  assert_eval("(equal? [1 2] [1 2 3])", "false");
+  assert_eval("(= (list 1 2) (list 1 2) (list 1 2))", "true");
+  assert_eval("(!= (list 1) (list 1 1))", "true");
+}
+
+#[test]
+fn arity() {
+  assert!(matches!(
+    eval("(eq? 1)"),
+    Err(RuntimeError::TooFewArguments { .. }),
+  ));
+  assert!(matches!(
+    eval("(=)"),
+    Err(RuntimeError::TooFewArguments { min: 2, max: None, got: 0 }),
+  ));
+}
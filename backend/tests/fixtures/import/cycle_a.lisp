@@ -0,0 +1,3 @@
+(import "cycle_b")
+
+(def a 1)
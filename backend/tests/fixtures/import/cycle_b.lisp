@@ -0,0 +1,3 @@
+(import "cycle_a")
+
+(def b 2)
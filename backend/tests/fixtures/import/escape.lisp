@@ -0,0 +1,6 @@
+(module escape (export run))
+
+(def run
+  (lambda ()
+    (println "escaped the sandbox")
+    (exit 7)))
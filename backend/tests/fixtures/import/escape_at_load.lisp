@@ -0,0 +1,2 @@
+(println "escaped the sandbox")
+(exit 7)
@@ -0,0 +1 @@
+(def double (lambda (x) (* 2 x)))
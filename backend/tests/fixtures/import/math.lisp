@@ -0,0 +1,5 @@
+(module math (export square cube))
+
+(def square (lambda (x) (* x x)))
+(def cube (lambda (x) (* x (square x))))
+(def hidden 42)
@@ -0,0 +1,3 @@
+(write-string "loaded ")
+
+(def state [])
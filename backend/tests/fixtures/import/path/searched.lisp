@@ -0,0 +1 @@
+(def found :search-path)
@@ -0,0 +1,5 @@
+(module relative (export quadruple))
+
+(import "lib/helper" (only double))
+
+(def quadruple (lambda (x) (double (double x))))
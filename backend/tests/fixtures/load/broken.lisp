@@ -0,0 +1,3 @@
+(def ok 1)
+
+(def x ))
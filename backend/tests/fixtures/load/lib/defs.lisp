@@ -0,0 +1,3 @@
+(load "more.lisp")
+
+(def x 1)
@@ -0,0 +1 @@
+(load "broken.lisp")
@@ -0,0 +1,3 @@
+(load "lib/defs.lisp")
+
+(list x (helper))
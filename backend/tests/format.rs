@@ -0,0 +1,53 @@
+mod common;
+
+use common::{assert_eval, eval};
+
+#[test]
+fn display_and_write() {
+  assert_eval(r#"(format "~a and ~s" "x" "y")"#, r#"x and "y""#);
+  assert_eval(r#"(format "~a~%~~" (list 1 #\a))"#, "(1 a)\n~");
+  assert_eval(r#"(format "[~5a]" "ab")"#, "[ab   ]");
+  assert_eval(r#"(format "[~5@a]" "ab")"#, "[   ab]");
+}
+
+#[test]
+fn integers() {
+  assert_eval(r#"(format "~d ~x ~o ~b" 42 255 8 5)"#, "42 ff 10 101");
+  assert_eval(r#"(format "~5,'0d|~4d|~@d" 42 -7 3)"#, "00042|  -7|+3");
+  assert_eval(r#"(format "~x" -255)"#, "-ff");
+}
+
+#[test]
+fn floats() {
+  assert_eval(r#"(format "~,2f" 3.14159)"#, "3.14");
+  assert_eval(r#"(format "~8,3f|" 2)"#, "   2.000|");
+  assert_eval(r#"(format "~f" 1.5)"#, "1.5");
+}
+
+#[test]
+fn destinations() {
+  assert_eval(r#"(format false "~d" 1)"#, "1");
+  assert_eval(
+    r#"(let ((port (open-output-string)))
+         (format port "~a-~a" 1 2)
+         (get-output-string port))"#,
+    "1-2",
+  );
+  assert_eval(r#"(with-output-to-string (lambda () (format true "~a" "out")))"#, "out");
+}
+
+#[test]
+fn errors() {
+  for input in [
+    r#"(format "~a ~a" 1)"#,
+    r#"(format "~a" 1 2)"#,
+    r#"(format "~q" 1)"#,
+    r#"(format "~")"#,
+    r#"(format "~d" "x")"#,
+  ] {
+    assert!(eval(input).is_err(), "{} should fail", input);
+  }
+
+  let err = eval(r#"(format "~a ~d" 1)"#).unwrap_err().to_string();
+  assert!(err.contains("FormatError") && err.contains("~d"), "{}", err);
+}
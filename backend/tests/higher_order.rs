@@ -0,0 +1,94 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn map() {
+  assert_eval("(map (lambda (x) (* x x)) (list 1 2 3))", "(1 4 9)");
+  assert_eval("(map + (list 1 2 3) (list 10 20))", "(11 22)");
+  assert_eval("(map car ())", "()");
+  assert!(matches!(
+    eval("(map car)"),
+    Err(RuntimeError::TooFewArguments { min: 2, max: None, got: 1 }),
+  ));
+  assert!(matches!(eval("(map 1 (list 1))"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn for_each() {
+  assert_eval(
+    "(def v []) (for-each (lambda (x) (vector-push! v x)) (list 1 2)) v",
+    "[1 2]",
+  );
+  assert_eval("(for-each car ())", "()");
+}
+
+#[test]
+fn filter() {
+  assert_eval("(filter (lambda (x) (> x 1)) (list 1 2 3))", "(2 3)");
+  assert_eval("(filter (lambda (x) x) (list 1 false () 2))", "(1 2)");
+}
+
+#[test]
+fn reduce() {
+  assert_eval("(reduce + (list 1 2 3))", "6");
+  assert_eval("(reduce + 10 (list 1 2 3))", "16");
+  assert_eval("(reduce + 10 ())", "10");
+  assert_eval("(reduce - (list 10 1 2))", "7");
+  assert!(matches!(eval("(reduce + ())"), Err(RuntimeError::NilValue { .. })));
+}
+
+#[test]
+fn folds() {
+  assert_eval("(fold-left (lambda (acc x) (cons x acc)) () (list 1 2 3))", "(3 2 1)");
+  assert_eval("(fold-left - 0 (list 1 2 3))", "-6");
+  assert_eval("(fold-right cons () (list 1 2 3))", "(1 2 3)");
+  assert_eval("(fold-right - 0 (list 1 2 3))", "2");
+  assert_eval("(fold-right cons () ())", "()");
+}
+
+#[test]
+fn apply() {
+  assert_eval("(apply + (list 1 2 3))", "6");
+  assert_eval("(apply + 1 2 (list 3))", "6");
+  assert_eval("(apply list ())", "()");
+  assert!(matches!(eval("(apply + 1)"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn sort() {
+  assert_eval("(sort (list 3 1 2))", "(1 2 3)");
+  assert_eval("(sort (list \"b\" \"a\"))", "(a b)");
+  assert_eval("(sort ())", "()");
+  assert_eval("(sort (list 1 2 3) >)", "(3 2 1)");
+  assert!(matches!(eval("(sort (list 1 \"a\"))"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn sort_is_stable() {
+  assert_eval(
+    "(sort
+      (list (list 1 :a) (list 0 :b) (list 1 :c) (list 0 :d) (list 1 :e))
+      (lambda (a b) (< (car a) (car b))))",
+    "((0 :b) (0 :d) (1 :a) (1 :c) (1 :e))",
+  );
+}
+
+#[test]
+fn any_every() {
+  assert_eval("(any? (lambda (x) (> x 2)) (list 1 2 3))", "true");
+  assert_eval("(any? (lambda (x) (> x 5)) (list 1 2 3))", "false");
+  assert_eval("(any? car ())", "false");
+  assert_eval("(every? (lambda (x) (> x 0)) (list 1 2 3))", "true");
+  assert_eval("(every? (lambda (x) (> x 1)) (list 1 2 3))", "false");
+  assert_eval("(every? car ())", "true");
+}
+
+#[test]
+fn errors_propagate() {
+  assert!(matches!(
+    eval("(map (lambda (x) (car x)) (list 1))"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+}
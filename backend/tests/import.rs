@@ -0,0 +1,161 @@
+use std::path::PathBuf;
+use lispers_common::{backend::DefaultBackend, symbol::SymbolUsize};
+use lispers_backend::{Interpreter, RuntimeError};
+
+type Symbol = SymbolUsize;
+type Backend = DefaultBackend<Symbol>;
+
+fn fixtures() -> PathBuf {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/import")
+}
+
+/// Evaluate `input` with `{}` replaced by the fixtures directory.
+fn eval_in(interpreter: &mut Interpreter<Symbol, Backend>, input: &str) -> Result<String, RuntimeError> {
+  let env = interpreter.default_env();
+  let input = input.replace("{}", &fixtures().display().to_string());
+  let value = interpreter.eval_string(env, &input)?;
+  Ok(interpreter.format_value(&value))
+}
+
+fn eval(input: &str) -> Result<String, RuntimeError> {
+  eval_in(&mut Interpreter::new(), input)
+}
+
+fn assert_eval(input: &str, expected: &str) {
+  match eval(input) {
+    Ok(output) => assert_eq!(output, expected, "while evaluating {}", input),
+    Err(err) => panic!("while evaluating {}: {}", input, err),
+  }
+}
+
+fn assert_import_error_in(
+  interpreter: &mut Interpreter<Symbol, Backend>,
+  input: &str,
+  detail: &str,
+) {
+  match eval_in(interpreter, input) {
+    Err(RuntimeError::ImportError { detail: got }) => {
+      assert!(got.contains(detail), "{:?} does not contain {:?}", got, detail);
+    },
+    other => panic!("{} should fail to import, got {:?}", input, other.map_err(|e| e.to_string())),
+  }
+}
+
+fn assert_import_error(input: &str, detail: &str) {
+  assert_import_error_in(&mut Interpreter::new(), input, detail);
+}
+
+#[test]
+fn exports() {
+  assert_eval("(import \"{}/math\") (list (square 3) (cube 2))", "(9 8)");
+  assert!(matches!(
+    eval("(import \"{}/math\") hidden"),
+    Err(RuntimeError::UndefinedSymbol { .. }),
+  ));
+}
+
+#[test]
+fn every_definition_is_exported_without_module_form() {
+  assert_eval("(import \"{}/all.lisp\") (list a b)", "(1 2)");
+}
+
+#[test]
+fn only() {
+  assert_eval("(import \"{}/math\" (only square)) (square 4)", "16");
+  assert!(matches!(
+    eval("(import \"{}/math\" (only square)) cube"),
+    Err(RuntimeError::UndefinedSymbol { .. }),
+  ));
+  assert_import_error("(import \"{}/math\" (only hidden))", "does not export hidden");
+}
+
+#[test]
+fn prefix() {
+  assert_eval("(import \"{}/math\" (prefix m:)) (m:square 5)", "25");
+  assert!(matches!(
+    eval("(import \"{}/math\" (prefix m:)) square"),
+    Err(RuntimeError::UndefinedSymbol { .. }),
+  ));
+  assert_eval("(import \"{}/math\" (only cube) (prefix m/)) (m/cube 3)", "27");
+}
+
+#[test]
+fn invalid_clause() {
+  assert!(matches!(
+    eval("(import \"{}/math\" (rename square sq))"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+}
+
+#[test]
+fn relative_to_importing_file() {
+  assert_eval("(import \"{}/relative\") (quadruple 3)", "12");
+  assert!(matches!(
+    eval("(import \"{}/relative\") double"),
+    Err(RuntimeError::UndefinedSymbol { .. }),
+  ));
+}
+
+#[test]
+fn search_paths() {
+  let mut interpreter = Interpreter::new();
+  assert_import_error_in(&mut interpreter, "(import \"searched\")", "module \"searched\" not found");
+
+  let mut interpreter = Interpreter::new();
+  interpreter.add_search_path(fixtures().join("path"));
+  assert_eq!(eval_in(&mut interpreter, "(import \"searched\") found").unwrap(), ":search-path");
+}
+
+#[test]
+fn not_found() {
+  assert_import_error("(import \"{}/missing\")", "module \"");
+}
+
+#[test]
+fn modules_are_cached() {
+  assert_eval(
+    "(with-output-to-string (lambda ()
+       (import \"{}/noisy\")
+       (import \"{}/noisy.lisp\" (prefix again:))))",
+    "loaded ",
+  );
+  assert_eval(
+    "(import \"{}/noisy\")
+     (import \"{}/noisy\" (prefix again:))
+     (vector-push! state 1)
+     (list (eq? state again:state) again:state)",
+    "(true [1])",
+  );
+}
+
+#[test]
+fn cache_is_per_interpreter() {
+  let mut interpreter = Interpreter::new();
+  eval_in(&mut interpreter, "(import \"{}/noisy\") (vector-push! state 1)").unwrap();
+  assert_eq!(eval_in(&mut interpreter, "(import \"{}/noisy\") state").unwrap(), "[1]");
+  assert_eq!(eval("(import \"{}/noisy\") state").unwrap(), "[]");
+}
+
+#[test]
+fn cyclic_imports() {
+  assert_import_error("(import \"{}/cycle_a\")", "cyclic import");
+
+  match eval("(import \"{}/cycle_a\")") {
+    Err(RuntimeError::ImportError { detail }) => {
+      assert!(detail.contains("cycle_a.lisp -> "), "{}", detail);
+      assert!(detail.contains("cycle_b.lisp -> "), "{}", detail);
+      assert!(detail.ends_with("cycle_a.lisp"), "{}", detail);
+    },
+    other => panic!("expected an ImportError, got {:?}", other.map_err(|e| e.to_string())),
+  }
+}
+
+#[test]
+fn failed_import_is_not_cached() {
+  let mut interpreter = Interpreter::new();
+  assert!(eval_in(&mut interpreter, "(import \"{}/cycle_a\")").is_err());
+  assert!(matches!(
+    eval_in(&mut interpreter, "(import \"{}/cycle_a\")"),
+    Err(RuntimeError::ImportError { .. }),
+  ));
+}
@@ -0,0 +1,252 @@
+use std::path::{Path, PathBuf};
+use lispers_common::{backend::DefaultBackend, symbol::SymbolUsize};
+use lispers_backend::{Interpreter, RuntimeError};
+
+type Symbol = SymbolUsize;
+type Backend = DefaultBackend<Symbol>;
+
+/// Path of a scratch file, unique to the test and the process.
+fn temp_file(name: &str, contents: Option<&str>) -> PathBuf {
+  let path = std::env::temp_dir().join(format!("lispers-io-{}-{}", std::process::id(), name));
+
+  if let Some(contents) = contents {
+    std::fs::write(&path, contents).unwrap();
+  }
+
+  path
+}
+
+/// Evaluate `input` with `PATH` replaced by the quoted `path`.
+fn eval(path: &Path, input: &str) -> Result<String, RuntimeError> {
+  let mut interpreter: Interpreter<Symbol, Backend> = Interpreter::new();
+  let env = interpreter.default_env();
+  let input = input.replace("PATH", &format!("{:?}", path.display().to_string()));
+  let value = interpreter.eval_string(env, &input)?;
+  Ok(interpreter.format_value(&value))
+}
+
+fn assert_eval(path: &Path, input: &str, expected: &str) {
+  match eval(path, input) {
+    Ok(output) => assert_eq!(output, expected, "while evaluating {}", input),
+    Err(err) => panic!("while evaluating {}: {}", input, err),
+  }
+}
+
+fn assert_io_error(path: &Path, input: &str, detail: &str) {
+  match eval(path, input) {
+    Err(err @ RuntimeError::IOError(..)) => {
+      assert!(err.to_string().contains(detail), "{} does not contain {:?}", err, detail);
+    },
+    other => panic!("{} should fail with an IOError, got {:?}", input, other.map_err(|e| e.to_string())),
+  }
+}
+
+#[test]
+fn write_then_read_file() {
+  let path = temp_file("roundtrip", None);
+
+  assert_eval(
+    &path,
+    "(def out (open-output-file PATH))
+     (write-string \"héllo\" out)
+     (write-string \"\\nworld\" out)
+     (close-port out)
+     (def in (open-input-file PATH))
+     (list (read-line in) (read-line in) (read-line in))",
+    "(héllo world ())",
+  );
+
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn read_line() {
+  let path = temp_file("read-line", Some("a\r\nb\n\nc\rd\ne"));
+
+  assert_eval(
+    &path,
+    "(def in (open-input-file PATH))
+     (list (read-line in) (read-line in) (read-line in) (read-line in) (read-line in) (read-line in))",
+    "(a b  c\rd e ())",
+  );
+
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn read_line_at_eof() {
+  let path = temp_file("read-line-eof", Some(""));
+  assert_eval(&path, "(read-line (open-input-file PATH))", "()");
+  assert_eval(&path, "(null? (read-line (open-input-string \"\")))", "true");
+  assert_eval(&path, "(read-line (open-input-string \"x\\r\\n\"))", "x");
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn read_char() {
+  let path = temp_file("read-char", Some("aé\n"));
+
+  assert_eval(
+    &path,
+    "(def in (open-input-file PATH))
+     (list (read-char in) (char->integer (read-char in)) (char->integer (read-char in)) (read-char in))",
+    "(a 233 10 ())",
+  );
+  assert_eval(
+    &path,
+    "(def in (open-input-string \"é!\")) (list (read-char in) (read-char in) (read-char in))",
+    "(é ! ())",
+  );
+
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn read_char_across_buffer_boundary() {
+  let contents = format!("{}é", "a".repeat(8191));
+  let path = temp_file("boundary", Some(&contents));
+
+  assert_eval(
+    &path,
+    "(def in (open-input-file PATH))
+     (do ((c (read-char in) (read-char in))
+          (n 0 (+ n 1))
+          (last () c))
+         ((null? c) (list n last)))",
+    "(8192 é)",
+  );
+
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn read_datum_across_buffer_boundary() {
+  let contents = format!("{}\"é\" done", " ".repeat(8190));
+  let path = temp_file("datum-boundary", Some(&contents));
+
+  assert_eval(
+    &path,
+    "(def in (open-input-file PATH)) (list (read in) (read in) (read in))",
+    "(é done ())",
+  );
+
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn read_line_after_read() {
+  let path = temp_file("read-then-line", Some("abc def\nnext"));
+
+  assert_eval(
+    &path,
+    "(def in (open-input-file PATH)) (list (read in) (read-line in) (read-line in))",
+    "(abc  def next)",
+  );
+
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn invalid_utf8() {
+  let path = temp_file("invalid-utf8", None);
+  std::fs::write(&path, b"a\xff").unwrap();
+
+  assert_io_error(
+    &path,
+    "(def in (open-input-file PATH)) (read-char in) (read-char in)",
+    "valid UTF-8",
+  );
+
+  std::fs::write(&path, b"a\xc3").unwrap();
+  assert_io_error(
+    &path,
+    "(def in (open-input-file PATH)) (read-char in) (read-char in)",
+    "valid UTF-8",
+  );
+
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn string_ports() {
+  let path = PathBuf::new();
+
+  assert_eval(&path, "(read-line (open-input-string \"a\\nb\"))", "a");
+  assert_eval(
+    &path,
+    "(def out (open-output-string)) (write-string \"a\" out) (write 1.5 out) (get-output-string out)",
+    "a1.5",
+  );
+  assert_eval(&path, "(with-output-to-string (lambda () (write-string \"x\")))", "x");
+}
+
+#[test]
+fn close_port() {
+  let path = temp_file("close", Some("line"));
+
+  assert_eval(&path, "(def in (open-input-file PATH)) (close-port in) (close-port in)", "()");
+  assert_eval(&path, "(def out (open-output-string)) (close-port out) (close-port out)", "()");
+  assert_io_error(&path, "(def in (open-input-file PATH)) (close-port in) (read-line in)", "is closed");
+  assert_io_error(&path, "(def in (open-input-file PATH)) (close-port in) (read-char in)", "is closed");
+  assert_io_error(&path, "(def out (open-output-string)) (close-port out) (write-string \"x\" out)", "is closed");
+
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn close_flushes_output() {
+  let path = temp_file("flush", None);
+
+  eval(&path, "(def out (open-output-file PATH)) (write-string \"data\" out) (close-port out)").unwrap();
+  assert_eq!(std::fs::read_to_string(&path).unwrap(), "data");
+
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn call_with_input_file() {
+  let path = temp_file("call-with", Some("first\nsecond"));
+
+  assert_eval(&path, "(call-with-input-file PATH read-line)", "first");
+  assert_io_error(
+    &path,
+    "(def saved []) (call-with-input-file PATH (lambda (p) (vector-push! saved p))) (read-line (vector-ref saved 0))",
+    "is closed",
+  );
+
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn call_with_input_file_closes_on_error() {
+  let path = temp_file("call-with-error", Some("line"));
+  let mut interpreter: Interpreter<Symbol, Backend> = Interpreter::new();
+  let env = interpreter.default_env();
+  let input = format!(
+    "(def saved []) (call-with-input-file {:?} (lambda (p) (vector-push! saved p) (car 1)))",
+    path.display().to_string(),
+  );
+
+  let result = interpreter.eval_string(env.clone(), &input);
+  assert!(matches!(result, Err(RuntimeError::TypeError { .. })));
+
+  let result = interpreter.eval_string(env, "(read-line (vector-ref saved 0))");
+  assert!(matches!(&result, Err(err @ RuntimeError::IOError(..)) if err.to_string().contains("is closed")));
+
+  std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn io_errors() {
+  let missing = temp_file("missing", None);
+
+  assert_io_error(&missing, "(open-input-file PATH)", "IOError");
+  assert_io_error(&missing, "(call-with-input-file PATH read-line)", "IOError");
+  assert_io_error(&missing, "(read-line (open-output-string))", "is not an input port");
+  assert_io_error(&missing, "(read-char (current-output-port))", "is not an input port");
+  assert_io_error(&missing, "(write-string \"x\" (open-input-string \"\"))", "is not an output port");
+  assert_io_error(&missing, "(get-output-string (open-input-string \"\"))", "is not a string output port");
+
+  let dir = std::env::temp_dir().join("lispers-io-missing-dir").join("file");
+  assert_io_error(&dir, "(open-output-file PATH)", "IOError");
+}
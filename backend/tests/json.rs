@@ -0,0 +1,107 @@
+mod common;
+
+use common::{assert_eval, eval};
+
+#[test]
+fn parse_scalars() {
+  assert_eval(r#"(json-parse "42")"#, "42");
+  assert_eval(r#"(json-parse "-1.5e2")"#, "-150");
+  assert_eval(r#"(float? (json-parse "1.0"))"#, "true");
+  assert_eval(r#"(json-parse "-9223372036854775808")"#, "-9223372036854775808");
+  assert_eval(r#"(float? (json-parse "1e308"))"#, "true");
+  assert_eval(r#"(float? (json-parse "18446744073709551616"))"#, "true");
+  assert_eval(r#"(= (json-parse "-18446744073709551616") -18446744073709551616.0)"#, "true");
+  assert_eval(r#"(json-parse " true ")"#, "true");
+  assert_eval(r#"(null? (json-parse "null"))"#, "true");
+}
+
+#[test]
+fn parse_strings() {
+  assert_eval(r#"(json-parse "\"a\\\"b\\\\c\\/\\n\"")"#, "a\"b\\c/\n");
+  assert_eval(r#"(json-parse "\"\\u00e9\\u4e2d\"")"#, "é中");
+  assert_eval(r#"(json-parse "\"\\ud83d\\ude00\"")"#, "😀");
+  assert_eval(r#"(json-parse "\"é😀\"")"#, "é😀");
+}
+
+#[test]
+fn parse_containers() {
+  assert_eval(r#"(json-parse "[1, [2, \"x\"], {}]")"#, "[1 [2 x] {}]");
+  assert_eval(r#"(get (json-parse "{\"a\": {\"b\": [true]}}") "a")"#, "{b [true]}");
+  assert_eval(r#"(:a (json-parse "{\"a\": 1}" :keys :keyword))"#, "1");
+}
+
+#[test]
+fn parse_errors() {
+  let cases = [
+    (r#"(json-parse "")"#, "unexpected end of input at line 1, column 1"),
+    (r#"(json-parse "[1,]")"#, "unexpected character ']' at line 1, column 4"),
+    (r#"(json-parse "{\n  \"a\" 1}")"#, "unexpected character '1' at line 2, column 7"),
+    (r#"(json-parse "[1] x")"#, "unexpected character 'x' at line 1, column 5"),
+    (r#"(json-parse "01")"#, "unexpected character '1' at line 1, column 2"),
+    (r#"(json-parse "\"\\ud83d\"")"#, "lone surrogate \\ud83d at line 1, column 2"),
+    (r#"(json-parse "\"\\x\"")"#, "invalid escape \\x at line 1, column 3"),
+    (r#"(json-parse "\"abc")"#, "unterminated string at line 1, column 5"),
+    (r#"(json-parse "[1e400]")"#, "number 1e400 out of range at line 1, column 2"),
+    (r#"(json-parse "-1e400")"#, "number -1e400 out of range at line 1, column 1"),
+    (r#"(json-parse "{\n\"a\": 1e999}")"#, "number 1e999 out of range at line 2, column 6"),
+    (r#"(json-parse "{\"a\": 1, \"a\": 2}")"#, "duplicate key \"a\" at line 1, column 10"),
+    (r#"(json-parse "[{}, {\"b\": {\"a\": 1, \"a\": 1}}]" :keys :keyword)"#, "duplicate key \"a\" at line 1, column 21"),
+  ];
+
+  for (input, expected) in cases {
+    let err = eval(input).unwrap_err().to_string();
+    assert_eq!(err, format!("JsonError: {}", expected), "while evaluating {}", input);
+  }
+}
+
+#[test]
+fn parse_nesting_limit() {
+  let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+  assert_eval(&format!("(vector? (json-parse \"{}\"))", nested(512)), "true");
+  assert_eq!(
+    eval(&format!("(json-parse \"{}\")", nested(513))).unwrap_err().to_string(),
+    "JsonError: nesting deeper than 512 levels at line 1, column 513",
+  );
+  assert!(eval("(json-parse (list->string (map (constantly #\\[) (range 20000))))").is_err());
+  assert!(eval("(json-parse (string-join (map (constantly \"{\\\"a\\\":\") (range 20000)) \"\"))").is_err());
+}
+
+#[test]
+fn stringify() {
+  assert_eval(r#"(json-stringify (list 1 2.5 "a\"b\n" true ()))"#, r#"[1,2.5,"a\"b\n",true,null]"#);
+  assert_eval(r#"(json-stringify 1.0)"#, "1.0");
+  assert_eval(r#"(json-stringify {:b [1] "a" :x})"#, r#"{"a":"x","b":[1]}"#);
+  assert_eval(r#"(json-stringify "\u{1}é")"#, "\"\\u0001é\"");
+  assert_eval(
+    r#"(json-stringify {:a [1 2] :b {}} :pretty true)"#,
+    "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": {}\n}",
+  );
+  assert_eval(r#"(json-stringify [[]] :pretty 4)"#, "[\n    []\n]");
+}
+
+#[test]
+fn stringify_errors() {
+  assert!(eval("(json-stringify (quote sym))").is_err());
+  assert!(eval("(json-stringify {1 2})").is_err());
+  assert!(eval("(json-stringify car)").is_err());
+  assert_eq!(
+    eval("(json-stringify 1 :pretty)").unwrap_err().to_string(),
+    "TypeError: expected <KeyValuePairs> but got <an odd number of key/value arguments (1)>",
+  );
+
+  assert_eq!(
+    eval(r#"(json-stringify {:b 1 :a 1 "a" 2})"#).unwrap_err().to_string(),
+    r#"JsonError: duplicate key "a" in object"#,
+  );
+  assert!(eval(r#"(json-stringify [(persistent-hash-map "k" 1 :k 2)])"#).is_err());
+}
+
+#[test]
+fn round_trip() {
+  assert_eval(
+    r#"(let ((text "{\"a\":[1,2.5,\"é\"],\"b\":{\"c\":null}}"))
+         (= text (json-stringify (json-parse text))))"#,
+    "true",
+  );
+}
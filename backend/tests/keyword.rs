@@ -0,0 +1,54 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn self_evaluating() {
+  assert_eval(":a", ":a");
+  assert_eval("(list :a :b-c)", "(:a :b-c)");
+  assert_eval("(keyword? :a)", "true");
+  assert_eval("(keyword? (quote a))", "false");
+}
+
+#[test]
+fn comment_after_keyword() {
+  assert_eval("(list :a;comment\n :b)", "(:a :b)");
+  assert_eval(":a; trailing comment", ":a");
+  assert_eval("(read \":a;comment\")", ":a");
+}
+
+#[test]
+fn equality() {
+  assert_eval("(= :a :a)", "true");
+  assert_eval("(= :a :b)", "false");
+  assert_eval("(eq? :a :a)", "true");
+}
+
+#[test]
+fn lookup_in_call_position() {
+  assert_eval("(:a {:a 1 :b 2})", "1");
+  assert_eval("(:c {:a 1})", "()");
+  assert_eval("(:c {:a 1} 0)", "0");
+  assert_eval("(:a (persistent-hash-map :a 1))", "1");
+  assert_eval("(def m {:x {:y 2}}) (:y (:x m))", "2");
+}
+
+#[test]
+fn lookup_as_function_value() {
+  assert_eval("(map :a (list {:a 1} {:a 2} {}))", "(1 2 ())");
+  assert_eval("(apply :a (list {:a 1}))", "1");
+}
+
+#[test]
+fn lookup_errors() {
+  assert!(matches!(eval("(:a (list 1))"), Err(RuntimeError::TypeError { .. })));
+  assert!(matches!(
+    eval("(:a)"),
+    Err(RuntimeError::TooFewArguments { min: 1, max: Some(2), got: 0 }),
+  ));
+  assert!(matches!(
+    eval("(:a {} 1 2)"),
+    Err(RuntimeError::TooManyArguments { min: 1, max: Some(2), got: 3 }),
+  ));
+}
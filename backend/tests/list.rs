@@ -0,0 +1,105 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn list() {
+  assert_eval("(list)", "()");
+  assert_eval("(list 1 (+ 1 1) 3)", "(1 2 3)");
+}
+
+#[test]
+fn cons() {
+  assert_eval("(cons 1 (list 2 3))", "(1 2 3)");
+  assert_eval("(cons (list 1) ())", "((1))");
+  assert!(matches!(eval("(cons 1 2)"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn car() {
+  assert_eval("(car (list 1 2 3))", "1");
+  assert!(matches!(eval("(car ())"), Err(RuntimeError::NilValue { .. })));
+}
+
+#[test]
+fn cdr() {
+  assert_eval("(cdr (list 1 2 3))", "(2 3)");
+  assert_eval("(cdr (list 1))", "()");
+  assert_eval("(cdr ())", "()");
+}
+
+#[test]
+fn null() {
+  assert_eval("(null? ())", "true");
+  assert_eval("(null? (list 1))", "false");
+  assert_eval("(null? 0)", "false");
+}
+
+#[test]
+fn length() {
+  assert_eval("(length ())", "0");
+  assert_eval("(length (list 1 2 3))", "3");
+}
+
+#[test]
+fn append() {
+  assert_eval("(append)", "()");
+  assert_eval("(append (list 1 2) () (list 3) (list 4 5))", "(1 2 3 4 5)");
+}
+
+#[test]
+fn reverse() {
+  assert_eval("(reverse ())", "()");
+  assert_eval("(reverse (list 1 2 3))", "(3 2 1)");
+}
+
+#[test]
+fn nth() {
+  assert_eval("(nth (list 1 2 3) 0)", "1");
+  assert_eval("(nth (list 1 2 3) 2)", "3");
+  assert!(matches!(eval("(nth (list 1 2 3) 3)"), Err(RuntimeError::IndexOutOfBounds { .. })));
+  assert!(matches!(eval("(nth (list 1 2 3) -1)"), Err(RuntimeError::IndexOutOfBounds { .. })));
+}
+
+#[test]
+fn last() {
+  assert_eval("(last (list 1 2 3))", "3");
+  assert!(matches!(eval("(last ())"), Err(RuntimeError::NilValue { .. })));
+}
+
+#[test]
+fn take() {
+  assert_eval("(take (list 1 2 3) 2)", "(1 2)");
+  assert_eval("(take (list 1 2 3) 0)", "()");
+  assert_eval("(take (list 1 2 3) 5)", "(1 2 3)");
+}
+
+#[test]
+fn drop() {
+  assert_eval("(drop (list 1 2 3) 1)", "(2 3)");
+  assert_eval("(drop (list 1 2 3) 0)", "(1 2 3)");
+  assert_eval("(drop (list 1 2 3) 5)", "()");
+}
+
+#[test]
+fn member() {
+  assert_eval("(member 2 (list 1 2 3))", "(2 3)");
+  assert_eval("(member (list 2) (list 1 (list 2) 3))", "((2) 3)");
+  assert_eval("(member 4 (list 1 2 3))", "false");
+}
+
+#[test]
+fn assoc() {
+  assert_eval("(assoc 2 (list (list 1 \"one\") (list 2 \"two\")))", "(2 two)");
+  assert_eval("(assoc :b (quote ((:a 1) (:b 2))))", "(:b 2)");
+  assert_eval("(assoc 3 (list (list 1 \"one\")))", "false");
+  assert_eval("(assoc (list 1) (list (list (list 1) :x)))", "((1) :x)");
+  assert!(matches!(eval("(assoc 1 2)"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn list_copy() {
+  assert_eval("(list-copy (list 1 2 3))", "(1 2 3)");
+  assert_eval("(list-copy ())", "()");
+}
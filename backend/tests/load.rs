@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+use lispers_common::{backend::DefaultBackend, symbol::SymbolUsize};
+use lispers_backend::{Interpreter, RuntimeError};
+
+type Symbol = SymbolUsize;
+type Backend = DefaultBackend<Symbol>;
+
+fn fixture(name: &str) -> PathBuf {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/load").join(name)
+}
+
+fn eval_file(name: &str) -> Result<String, RuntimeError> {
+  let mut interpreter: Interpreter<Symbol, Backend> = Interpreter::new();
+  let env = interpreter.default_env();
+  let value = interpreter.eval_file(env, fixture(name))?;
+  Ok(interpreter.format_value(&value))
+}
+
+fn eval(input: &str) -> Result<String, RuntimeError> {
+  let mut interpreter: Interpreter<Symbol, Backend> = Interpreter::new();
+  let env = interpreter.default_env();
+  let value = interpreter.eval_string(env, input)?;
+  Ok(interpreter.format_value(&value))
+}
+
+#[test]
+fn relative_to_current_file() {
+  assert_eq!(eval_file("main.lisp").unwrap(), "(1 :more)");
+}
+
+#[test]
+fn evaluates_in_current_scope() {
+  let path = fixture("lib/more.lisp");
+  let input = format!("(let () (load {:?}) (helper))", path.display().to_string());
+  assert_eq!(eval(&input).unwrap(), ":more");
+
+  let input = format!("(let () (load {:?})) helper", path.display().to_string());
+  assert!(matches!(eval(&input), Err(RuntimeError::UndefinedSymbol { .. })));
+}
+
+#[test]
+fn yields_last_value() {
+  let input = format!("(load {:?})", fixture("main.lisp").display().to_string());
+  assert_eq!(eval(&input).unwrap(), "(1 :more)");
+}
+
+#[test]
+fn missing_file() {
+  assert!(matches!(eval("(load \"does/not/exist.lisp\")"), Err(RuntimeError::IOError(..))));
+}
+
+#[test]
+fn syntax_error_names_the_file() {
+  let path = fixture("broken.lisp");
+  let expected = format!("SyntaxError: {}[3;9] Unexpected token 'ParenClose'", path.display());
+
+  let err = eval_file("broken.lisp").unwrap_err();
+  assert_eq!(err.to_string(), expected);
+
+  let err = eval_file("loads_broken.lisp").unwrap_err();
+  assert_eq!(err.to_string(), expected);
+
+  let input = format!("(load {:?})", path.display().to_string());
+  let err = eval(&input).unwrap_err();
+  assert_eq!(err.to_string(), expected);
+}
+
+#[test]
+fn syntax_error_in_string_has_no_file() {
+  let err = eval("(def x ))").unwrap_err();
+  assert!(err.to_string().starts_with("SyntaxError: <>[1;"), "{}", err);
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn while_loop() {
+  assert_eval(
+    "(def n [0]) (while (< (vector-ref n 0) 5) (vector-set! n 0 (+ (vector-ref n 0) 1))) n",
+    "[5]",
+  );
+  assert_eval("(while false 1)", "()");
+  assert_eval("(def v []) (while (< (vector-length v) 3) (vector-push! v 0) (vector-push! v 1)) v", "[0 1 0 1]");
+}
+
+#[test]
+fn dotimes() {
+  assert_eval("(def v []) (dotimes (i 3) (vector-push! v i)) v", "[0 1 2]");
+  assert_eval("(dotimes (i 3))", "()");
+  assert_eval("(dotimes (i 3 i))", "3");
+  assert_eval("(def v []) (dotimes (i -1) (vector-push! v i)) v", "[]");
+  assert!(matches!(eval("(dotimes (i :a))"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn dolist() {
+  assert_eval("(def v []) (dolist (x (list 1 2)) (vector-push! v (* x 10))) v", "[10 20]");
+  assert_eval("(def v []) (dolist (x [3 4]) (vector-push! v x)) v", "[3 4]");
+  assert_eval("(def v []) (dolist (x (persistent-vector 5)) (vector-push! v x)) v", "[5]");
+  assert_eval("(dolist (x (list 1 2) (list :done x)))", "(:done ())");
+  assert!(matches!(eval("(dolist (x 1))"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn do_loop() {
+  assert_eval("(do ((i 0 (+ i 1)) (acc () (cons i acc))) ((= i 3) acc))", "(2 1 0)");
+  assert_eval("(do ((i 0 (+ i 1))) ((= i 3)))", "()");
+  assert_eval("(def v []) (do ((i 0 (+ i 1)) (k :k)) ((= i 2) k) (vector-push! v i)) v", "[0 1]");
+  assert_eval("(do ((i 0 (+ i 1)) (j 10 (- j 1))) ((= i j) (list i j)))", "(5 5)");
+}
+
+#[test]
+fn break_with_value() {
+  assert_eval("(while true (break 42))", "42");
+  assert_eval("(while true (break))", "()");
+  assert_eval("(dotimes (i 10) (if (= i 3) (break (* i 2))))", "6");
+  assert_eval("(dolist (x (list 1 2 3)) (when (> x 1) (break x)))", "2");
+  assert_eval("(do ((i 0 (+ i 1))) (false :never) (when (= i 4) (break i)))", "4");
+}
+
+#[test]
+fn break_skips_result_form() {
+  assert_eval("(dotimes (i 3 :finished) (break :broken))", ":broken");
+}
+
+#[test]
+fn break_exits_innermost_loop() {
+  assert_eval(
+    "(def v [])
+     (dotimes (i 2)
+       (dotimes (j 10)
+         (if (= j 2) (break))
+         (vector-push! v (list i j))))
+     v",
+    "[(0 0) (0 1) (1 0) (1 1)]",
+  );
+}
+
+fn assert_loop_error(input: &str, expected: &str) {
+  match eval(input) {
+    Err(err @ RuntimeError::LoopError { .. }) => {
+      assert_eq!(err.to_string(), format!("LoopError: {}", expected));
+    },
+    other => panic!("{} should fail with a LoopError, got {:?}", input, other.map_err(|e| e.to_string())),
+  }
+}
+
+#[test]
+fn break_does_not_cross_functions() {
+  assert_loop_error("(def f (lambda () (break 5))) (while true (f))", "break outside of a loop");
+  assert_loop_error("(def f (lambda () (continue))) (dotimes (i 3) (f))", "continue outside of a loop");
+  assert_loop_error(
+    "(dotimes (i 5) (map (lambda (x) (if (= i 2) (break x))) (list :a)))",
+    "break outside of a loop",
+  );
+  assert_eval(
+    "(def v [])
+     (dotimes (i 5)
+       (map (lambda (x) (dotimes (j 3) (if (= j 1) (break)) (vector-push! v (list i j)))) (list i))
+       (if (= i 1) (break)))
+     v",
+    "[(0 0) (1 0)]",
+  );
+}
+
+#[test]
+fn continue_skips_rest_of_body() {
+  assert_eval(
+    "(def v []) (dotimes (i 5) (if (even? i) (continue)) (vector-push! v i)) v",
+    "[1 3]",
+  );
+  assert_eval(
+    "(def v []) (dolist (x (list 1 2 3)) (when (= x 2) (continue)) (vector-push! v x)) v",
+    "[1 3]",
+  );
+  assert_eval(
+    "(def v []) (do ((i 0 (+ i 1))) ((= i 4) v) (when (= i 1) (continue)) (vector-push! v i))",
+    "[0 2 3]",
+  );
+  assert_eval(
+    "(def n [0]) (def v [])
+     (while (< (vector-ref n 0) 4)
+       (vector-set! n 0 (+ (vector-ref n 0) 1))
+       (when (= (vector-ref n 0) 2) (continue))
+       (vector-push! v (vector-ref n 0)))
+     v",
+    "[1 3 4]",
+  );
+}
+
+#[test]
+fn break_outside_loop() {
+  assert!(matches!(eval("(break)"), Err(RuntimeError::Break)));
+  assert!(matches!(eval("(break 1)"), Err(RuntimeError::Break)));
+  assert!(matches!(eval("((lambda () (break 1)))"), Err(RuntimeError::LoopError { .. })));
+  assert!(matches!(eval("(map (lambda (x) (break x)) (list 1))"), Err(RuntimeError::LoopError { .. })));
+  assert_eq!(eval("(break)").unwrap_err().to_string(), "LoopError: break outside of a loop");
+}
+
+#[test]
+fn continue_outside_loop() {
+  assert!(matches!(eval("(continue)"), Err(RuntimeError::Continue)));
+  assert_eq!(eval("(continue)").unwrap_err().to_string(), "LoopError: continue outside of a loop");
+}
+
+#[test]
+fn arity() {
+  assert!(matches!(
+    eval("(while true (break 1 2))"),
+    Err(RuntimeError::TooManyArguments { min: 0, max: Some(1), got: 2 }),
+  ));
+  assert!(matches!(
+    eval("(while true (continue 1))"),
+    Err(RuntimeError::TooManyArguments { min: 0, max: Some(0), got: 1 }),
+  ));
+}
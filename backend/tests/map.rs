@@ -0,0 +1,106 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn literal() {
+  assert_eval("{}", "{}");
+  assert_eval("{:a (+ 1 2)}", "{:a 3}");
+  assert_eval("(get {(+ 1 1) :two} 2)", ":two");
+}
+
+#[test]
+fn hash_map() {
+  assert_eval("(hash-map)", "{}");
+  assert_eval("(hash-map :a 1)", "{:a 1}");
+  assert_eval("(get (hash-map :a 1 :a 2) :a)", "2");
+  assert!(matches!(
+    eval("(hash-map :a 1 :b)"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+  assert_eq!(
+    eval("(persistent-hash-map :a)").unwrap_err().to_string(),
+    "TypeError: expected <KeyValuePairs> but got <an odd number of key/value arguments (1)>",
+  );
+}
+
+#[test]
+fn get() {
+  assert_eval("(get {:a 1 :b 2} :b)", "2");
+  assert_eval("(get {:a 1} :b)", "()");
+  assert_eval("(get {:a 1} :b 0)", "0");
+  assert_eval("(get {\"a\" 1 #\\a 2 (quote a) 3} #\\a)", "2");
+  assert!(matches!(eval("(get (list 1) 0)"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn assoc() {
+  assert_eval("(assoc {} :a 1)", "{:a 1}");
+  assert_eval("(get (assoc {:a 1} :b 2 :c 3) :c)", "3");
+  assert_eval("(def m {:a 1}) (assoc m :a 2) m", "{:a 1}");
+  assert!(matches!(
+    eval("(assoc {:a 1} (list :a 1))"),
+    Err(RuntimeError::TooFewArguments { min: 3, max: None, got: 2 }),
+  ));
+  assert!(matches!(
+    eval("(assoc {:a 1} :b 2 :c)"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+}
+
+#[test]
+fn dissoc() {
+  assert_eval("(dissoc {:a 1 :b 2} :b)", "{:a 1}");
+  assert_eval("(dissoc {:a 1} :b)", "{:a 1}");
+  assert_eval("(dissoc {:a 1 :b 2} :a :b)", "{}");
+}
+
+#[test]
+fn contains() {
+  assert_eval("(contains? {:a 1} :a)", "true");
+  assert_eval("(contains? {:a ()} :a)", "true");
+  assert_eval("(contains? {:a 1} :b)", "false");
+}
+
+#[test]
+fn keys_vals() {
+  assert_eval("(keys {})", "()");
+  assert_eval("(keys {:a 1})", "(:a)");
+  assert_eval("(vals {:a 1})", "(1)");
+  assert_eval("(length (keys {:a 1 :b 2 :c 3}))", "3");
+  assert_eval("(map->list {:a 1})", "((:a 1))");
+}
+
+#[test]
+fn unhashable_keys() {
+  assert!(matches!(
+    eval("(hash-map (list 1) 2)"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+  assert!(matches!(
+    eval("(hash-map 1.5 2)"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+  assert!(matches!(
+    eval("(assoc {} [1] 2)"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+  assert!(matches!(
+    eval("{(list 1) 2}"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+  assert!(matches!(
+    eval("{[1] 2}"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+  assert!(matches!(
+    eval("(quote {{} 2})"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+  assert!(matches!(
+    eval("(read \"{[1] 2}\")"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+  assert_eval("(get (quote {(quote a) 1}) (quote (quote a)))", "1");
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn literal_patterns() {
+  assert_eval("(match 1 (0 :zero) (1 :one))", ":one");
+  assert_eval("(match \"b\" (\"a\" 1) (\"b\" 2))", "2");
+  assert_eval("(match #\\x (#\\x :x))", ":x");
+  assert_eval("(match false (true 1) (false 0))", "0");
+  assert_eval("(match :b (:a 1) (:b 2))", "2");
+  assert_eval("(match 1.5 (1.5 :float))", ":float");
+}
+
+#[test]
+fn quoted_patterns() {
+  assert_eval("(match (quote b) ((quote a) 1) ((quote b) 2))", "2");
+  assert_eval("(match (list (quote add) 1 2) (((quote add) x y) (+ x y)))", "3");
+  assert_eval("(match (quote a) ((quote b) 1) (x x))", "a");
+}
+
+#[test]
+fn structural_patterns() {
+  assert_eval(
+    "(match (list :point 1 2) ((:circle r) r) ((:point x y) (+ x y)))",
+    "3",
+  );
+  assert_eval("(match [1 2 3] ([a] a) ([a . rest] rest))", "(2 3)");
+  assert_eval("(match {:k 1} ({v :k} v))", "1");
+}
+
+#[test]
+fn wildcard_and_binding() {
+  assert_eval("(match 5 (_ :any))", ":any");
+  assert_eval("(match 5 (x (* x 2)))", "10");
+}
+
+#[test]
+fn guards() {
+  assert_eval("(match 5 (x when (> x 10) :big) (x when (> x 0) :positive) (_ :other))", ":positive");
+  assert_eval("(match -1 (x when (> x 0) :positive) (_ :other))", ":other");
+  assert_eval("(match (list 1 2) ((a b) when (< a b) (list b a)))", "(2 1)");
+}
+
+#[test]
+fn first_matching_clause_wins() {
+  assert_eval("(match 1 (x :first) (1 :second))", ":first");
+  assert_eval("(match (list 1 2) ((a b) :pair) ((1 2) :exact))", ":pair");
+}
+
+#[test]
+fn bindings_are_scoped_to_the_clause() {
+  assert_eval("(def x 0) (match 1 (x x)) x", "0");
+  assert_eval("(def x 0) (match (list 1 2) ((x 3) x) (_ x))", "0");
+}
+
+#[test]
+fn multi_form_body() {
+  assert_eval("(match 1 (x (def v [x]) (vector-push! v 2) v))", "[1 2]");
+}
+
+#[test]
+fn no_match() {
+  match eval("(match 3 (1 :one) (2 :two))") {
+    Err(RuntimeError::MatchError { value }) => assert_eq!(value, "3"),
+    other => panic!("expected a MatchError, got {:?}", other.map_err(|e| e.to_string())),
+  }
+
+  assert!(matches!(eval("(match 1)"), Err(RuntimeError::MatchError { .. })));
+  assert!(matches!(
+    eval("(match 1 (x when false :never))"),
+    Err(RuntimeError::MatchError { .. }),
+  ));
+  assert_eq!(
+    eval("(match (list 1) (() :empty))").unwrap_err().to_string(),
+    "MatchError: no clause matches (1)",
+  );
+}
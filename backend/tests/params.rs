@@ -0,0 +1,102 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn required() {
+  assert_eval("((lambda (a b) (list a b)) 1 2)", "(1 2)");
+  assert_eval("((lambda () 1))", "1");
+}
+
+#[test]
+fn optional() {
+  assert_eval("((lambda (a &optional b) (list a b)) 1)", "(1 ())");
+  assert_eval("((lambda (a &optional (b 2)) (list a b)) 1)", "(1 2)");
+  assert_eval("((lambda (a &optional (b 2)) (list a b)) 1 3)", "(1 3)");
+  assert_eval("((lambda (a &optional (b (+ a 1)) (c (* b 2))) (list a b c)) 1)", "(1 2 4)");
+}
+
+#[test]
+fn rest() {
+  assert_eval("((lambda (a &rest more) more) 1)", "()");
+  assert_eval("((lambda (a &rest more) more) 1 2 3)", "(2 3)");
+  assert_eval("((lambda (a . more) (list a more)) 1 2)", "(1 (2))");
+  assert_eval("((lambda (&optional (a 0) &rest more) (list a more)) 1 2)", "(1 (2))");
+}
+
+#[test]
+fn key() {
+  assert_eval("((lambda (&key a (b 2)) (list a b)))", "(() 2)");
+  assert_eval("((lambda (&key a (b 2)) (list a b)) :b 3 :a 1)", "(1 3)");
+  assert_eval("((lambda (x &key (y x)) y) 5)", "5");
+  assert!(matches!(
+    eval("((lambda (&key a) a) :b 1)"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+  assert!(matches!(
+    eval("((lambda (&key a) a) 1 2)"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+}
+
+#[test]
+fn rest_and_key() {
+  assert_eval("((lambda (&rest all &key a) (list a all)) :a 1)", "(1 (:a 1))");
+  assert_eval("((lambda (&rest all &key a) a) :z 0 :a 1)", "1");
+}
+
+#[test]
+fn too_few_arguments() {
+  assert!(matches!(
+    eval("((lambda (a b) a) 1)"),
+    Err(RuntimeError::TooFewArguments { min: 2, max: Some(2), got: 1 }),
+  ));
+  assert!(matches!(
+    eval("((lambda (a &optional b) a))"),
+    Err(RuntimeError::TooFewArguments { min: 1, max: Some(2), got: 0 }),
+  ));
+  assert!(matches!(
+    eval("((lambda (a &rest b) a))"),
+    Err(RuntimeError::TooFewArguments { min: 1, max: None, got: 0 }),
+  ));
+}
+
+#[test]
+fn too_many_arguments() {
+  assert!(matches!(
+    eval("((lambda (a) a) 1 2)"),
+    Err(RuntimeError::TooManyArguments { min: 1, max: Some(1), got: 2 }),
+  ));
+  assert!(matches!(
+    eval("((lambda (a &optional b) a) 1 2 3)"),
+    Err(RuntimeError::TooManyArguments { min: 1, max: Some(2), got: 3 }),
+  ));
+  assert!(matches!(
+    eval("((lambda (&key a) a) :a 1 :a 2)"),
+    Err(RuntimeError::TooManyArguments { min: 0, max: Some(2), got: 4 }),
+  ));
+}
+
+#[test]
+fn arity_messages() {
+  let err = eval("((lambda (a &optional b) a) 1 2 3)").unwrap_err();
+  assert_eq!(
+    err.to_string(),
+    "ArityError: Too many arguments for function, expected 1 to 2 but got 3",
+  );
+
+  let err = eval("((lambda (a &rest b) a))").unwrap_err();
+  assert_eq!(
+    err.to_string(),
+    "ArityError: Too few arguments for function, expected at least 1 but got 0",
+  );
+}
+
+#[test]
+fn invalid_parameter_lists() {
+  assert!(matches!(eval("(lambda (&rest) 1)"), Err(RuntimeError::TypeError { .. })));
+  assert!(matches!(eval("(lambda (&rest a b) 1)"), Err(RuntimeError::TypeError { .. })));
+  assert!(matches!(eval("(lambda (&key a &optional b) 1)"), Err(RuntimeError::TypeError { .. })));
+  assert!(matches!(eval("(lambda (&optional (a 1 2)) 1)"), Err(RuntimeError::TypeError { .. })));
+}
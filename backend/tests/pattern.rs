@@ -0,0 +1,88 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn list_patterns() {
+  assert_eval("(let (((a b) (list 1 2))) (list b a))", "(2 1)");
+  assert_eval("(let (((a (b c)) (list 1 (list 2 3)))) (list a b c))", "(1 2 3)");
+  assert_eval("(let (((a _) (list 1 2))) a)", "1");
+}
+
+#[test]
+fn vector_patterns() {
+  assert_eval("(let (([a b] [1 2])) (list a b))", "(1 2)");
+  assert_eval("(let (([a b] (list 1 2))) (list a b))", "(1 2)");
+  assert_eval("(let (([a [b]] [1 [2]])) (+ a b))", "3");
+}
+
+#[test]
+fn rest_patterns() {
+  assert_eval("(let (((a b . rest) (list 1 2 3 4))) (list a b rest))", "(1 2 (3 4))");
+  assert_eval("(let (((a b . rest) (list 1 2))) rest)", "()");
+  assert_eval("(let (((a &rest rest) (list 1 2 3))) rest)", "(2 3)");
+  assert_eval("(let (([a &rest rest] [1 2 3])) rest)", "(2 3)");
+}
+
+#[test]
+fn literal_patterns() {
+  assert_eval("(let (((:ok x) (list :ok 1))) x)", "1");
+  assert_eval("(let (((1 \"a\" #\\b true x) (list 1 \"a\" #\\b true 2))) x)", "2");
+  assert_eval("(let ((((quote tag) x) (list (quote tag) 3))) x)", "3");
+}
+
+#[test]
+fn map_patterns() {
+  assert_eval("(let (({a :a b \"b\"} {:a 1 \"b\" 2})) (list a b))", "(1 2)");
+  assert_eval("(let (({a :a} {})) a)", "()");
+  assert_eval("(let (({[x y] :point} {:point [1 2]})) (+ x y))", "3");
+  assert_eval("(let (({a :a} (persistent-hash-map :a 1))) a)", "1");
+}
+
+#[test]
+fn keys_shorthand() {
+  assert_eval("(let (({:keys [a b]} {:a 1 :b 2})) (list a b))", "(1 2)");
+  assert_eval("(let (({:keys [a missing]} {:a 1})) (list a missing))", "(1 ())");
+}
+
+#[test]
+fn lambda_parameter_patterns() {
+  assert_eval("((lambda ((a b) c) (list a b c)) (list 1 2) 3)", "(1 2 3)");
+  assert_eval("((lambda ({:keys [x y]}) (* x y)) {:x 2 :y 3})", "6");
+  assert_eval("(map (lambda ([k v]) v) (list [:a 1] [:b 2]))", "(1 2)");
+}
+
+#[test]
+fn def_patterns() {
+  assert_eval("(def (a b) (list 1 2)) (+ a b)", "3");
+  assert_eval("(def {:keys [host port]} {:host \"h\" :port 80}) (list host port)", "(h 80)");
+}
+
+#[test]
+fn let_star_patterns() {
+  assert_eval("(let* (((a b) (list 1 2)) (c (+ a b))) c)", "3");
+}
+
+#[test]
+fn mismatch_names_the_pattern() {
+  match eval("(let (((a b) (list 1 2 3))) a)") {
+    Err(RuntimeError::PatternMismatch { pattern, value }) => {
+      assert_eq!(pattern, "(a b)");
+      assert_eq!(value, "(1 2 3)");
+    },
+    other => panic!("expected a PatternMismatch, got {:?}", other.map_err(|e| e.to_string())),
+  }
+
+  let err = eval("(def (:ok x) (list :error 1))").unwrap_err();
+  assert_eq!(err.to_string(), "PatternError: (:error 1) does not match pattern (:ok x)");
+
+  assert!(matches!(
+    eval("((lambda ((a b)) a) 1)"),
+    Err(RuntimeError::PatternMismatch { .. }),
+  ));
+  assert!(matches!(
+    eval("(let (({a :a} (list 1))) a)"),
+    Err(RuntimeError::PatternMismatch { .. }),
+  ));
+}
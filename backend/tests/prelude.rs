@@ -0,0 +1,142 @@
+mod common;
+
+use lispers_common::{backend::DefaultBackend, symbol::SymbolUsize};
+use lispers_backend::{Interpreter, RuntimeError};
+use common::{eval, assert_eval};
+
+#[test]
+fn bare_env() {
+  let mut interpreter: Interpreter<SymbolUsize, DefaultBackend<SymbolUsize>> = Interpreter::new();
+  let env = interpreter.bare_env();
+
+  assert!(interpreter.eval_string(env.clone(), "(car (list 1))").is_ok());
+  assert!(matches!(
+    interpreter.eval_string(env, "(cadr (list 1 2))"),
+    Err(RuntimeError::UndefinedSymbol { .. }),
+  ));
+}
+
+#[test]
+fn accessors() {
+  assert_eval("(first (list 1 2 3))", "1");
+  assert_eval("(second (list 1 2 3))", "2");
+  assert_eval("(third (list 1 2 3))", "3");
+  assert_eval("(rest (list 1 2 3))", "(2 3)");
+  assert_eval("(caar (list (list 1) 2))", "1");
+  assert_eval("(cadr (list 1 2 3))", "2");
+  assert_eval("(cdar (list (list 1 2) 3))", "(2)");
+  assert_eval("(cddr (list 1 2 3))", "(3)");
+  assert_eval("(caddr (list 1 2 3))", "3");
+}
+
+#[test]
+fn identity() {
+  assert_eval("(identity 1)", "1");
+}
+
+#[test]
+fn constantly() {
+  assert_eval("((constantly 1))", "1");
+  assert_eval("((constantly 1) 2 3)", "1");
+}
+
+#[test]
+fn complement() {
+  assert_eval("((complement null?) ())", "false");
+  assert_eval("((complement null?) (list 1))", "true");
+}
+
+#[test]
+fn partial() {
+  assert_eval("((partial + 1 2) 3 4)", "10");
+  assert_eval("((partial list))", "()");
+}
+
+#[test]
+fn compose() {
+  assert_eval("((compose) 1)", "1");
+  assert_eval("((compose inc (partial * 2)) 5)", "11");
+}
+
+#[test]
+fn inc_dec() {
+  assert_eval("(inc 1)", "2");
+  assert_eval("(dec 1)", "0");
+}
+
+#[test]
+fn sign_predicates() {
+  assert_eval("(list (zero? 0) (zero? 1))", "(true false)");
+  assert_eval("(list (positive? 1) (positive? 0))", "(true false)");
+  assert_eval("(list (negative? -1) (negative? 0))", "(true false)");
+}
+
+#[test]
+fn parity_predicates() {
+  assert_eval("(list (even? 4) (even? -3) (even? 0))", "(true false true)");
+  assert_eval("(list (odd? 3) (odd? -4))", "(true false)");
+}
+
+#[test]
+fn abs() {
+  assert_eval("(list (abs -3) (abs 3))", "(3 3)");
+}
+
+#[test]
+fn min_max() {
+  assert_eval("(min 3 1 2)", "1");
+  assert_eval("(max 3 1 2)", "3");
+  assert_eval("(max 7)", "7");
+  assert!(matches!(eval("(min)"), Err(RuntimeError::TooFewArguments { .. })));
+}
+
+#[test]
+fn sum_product() {
+  assert_eval("(sum (list 1 2 3))", "6");
+  assert_eval("(sum ())", "0");
+  assert_eval("(product (list 2 3 4))", "24");
+  assert_eval("(product ())", "1");
+}
+
+#[test]
+fn range() {
+  assert_eval("(range 4)", "(0 1 2 3)");
+  assert_eval("(range 2 5)", "(2 3 4)");
+  assert_eval("(range 0)", "()");
+  assert_eval("(range 5 2)", "()");
+}
+
+#[test]
+fn remove() {
+  assert_eval("(remove even? (range 6))", "(1 3 5)");
+}
+
+#[test]
+fn count() {
+  assert_eval("(count even? (range 6))", "3");
+}
+
+#[test]
+fn find() {
+  assert_eval("(find (lambda (x) (> x 2)) (list 1 3 5))", "3");
+  assert_eval("(find zero? (list 1 2))", "false");
+}
+
+#[test]
+fn zip() {
+  assert_eval("(zip (list 1 2 3) (list :a :b))", "((1 :a) (2 :b))");
+}
+
+#[test]
+fn flatten() {
+  assert_eval("(flatten (list 1 (list 2 (list 3 ())) 4))", "(1 2 3 4)");
+}
+
+#[test]
+fn long_lists() {
+  assert_eval("(length (range 20000))", "20000");
+  assert_eval("(last (range 5 20000))", "19999");
+  assert_eval("(find (lambda (x) (= x 19999)) (range 20000))", "19999");
+  assert_eval("(find negative? (range 20000))", "false");
+  assert_eval("(length (flatten (list (range 10000) (list (range 10000)) 1)))", "20001");
+}
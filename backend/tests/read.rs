@@ -0,0 +1,102 @@
+mod common;
+
+use common::assert_eval;
+
+const DATA: &[&str] = &[
+  "1",
+  "-2",
+  "1.0",
+  "-2.5",
+  "1e100",
+  "\"a \\\"quoted\\\" \\\\ string\\n\\t\\u{7} é\"",
+  "#\\a",
+  "#\\space",
+  "#\\newline",
+  "#\\(",
+  "#\\\"",
+  "#\\x7",
+  ":keyword",
+  "(quote symbol)",
+  "true",
+  "false",
+  "()",
+  "(list 1 (list 2.0 \"three\") #\\4)",
+  "[1 \"x\" #\\y]",
+  "#(1 #(2) \"x\")",
+  "{:a 1 \"b\" [2]}",
+  "(persistent-vector 1 2)",
+  "(persistent-hash-map :a \"x\")",
+  "(quote (quote x))",
+];
+
+#[test]
+fn write_read_round_trip() {
+  for datum in DATA {
+    let input = format!("(let ((x {})) (equal? x (read (write x))))", datum);
+    assert_eval(&input, "true");
+
+    let input = format!(
+      "(let ((x {})) (equal? x (read (open-input-string (string-append (write x) \" rest\")))))",
+      datum,
+    );
+    assert_eval(&input, "true");
+  }
+}
+
+#[test]
+fn display_and_write() {
+  assert_eval("(display \"a\\nb\")", "a\nb");
+  assert_eval("(write \"a\\nb\")", "\"a\\nb\"");
+  assert_eval("(display #\\a)", "a");
+  assert_eval("(write #\\a)", "#\\a");
+  assert_eval("(write 1.0)", "1.0");
+  assert_eval(
+    "(let ((p (open-output-string))) (write \"x\" p) (display \"y\" p) (get-output-string p))",
+    "\"x\"y",
+  );
+}
+
+#[test]
+fn non_finite_floats() {
+  assert_eval("(write (./ 1.0 0.0))", "+inf.0");
+  assert_eval("(write (./ -1.0 0.0))", "-inf.0");
+  assert_eval("(write (list (./ 0.0 0.0)))", "(-nan.0)");
+  assert_eval("(display (./ 1.0 0.0))", "inf");
+
+  for datum in ["(./ 1.0 0.0)", "(./ -1.0 0.0)", "[(./ 1.0 0.0)]"] {
+    let input = format!("(let ((x {})) (equal? x (read (write x))))", datum);
+    assert_eval(&input, "true");
+  }
+
+  // NaN is never `equal?` to itself, but reads back with the same bits.
+  for datum in ["(./ 0.0 0.0)", "+nan.0", "-nan.0"] {
+    let input = format!("(let ((x {})) (eqv? x (read (write x))))", datum);
+    assert_eval(&input, "true");
+  }
+
+  assert_eval("(float? (read \"+nan.0\"))", "true");
+  assert_eval("(= -inf.0 (read (open-input-string \"-inf.0\")))", "true");
+  assert_eval("(symbol? (read \"inf\"))", "true");
+}
+
+#[test]
+fn read_from_string() {
+  assert_eval("(read \"(a b) c\")", "(a b)");
+  assert_eval("(read \"\")", "()");
+  assert_eval("(read \"#(1 2) 3\")", "[1 2]");
+  assert_eval("(read \"ab#(1)\")", "ab#");
+}
+
+#[test]
+fn read_from_port() {
+  assert_eval(
+    "(let ((p (open-input-string \" ; comment\n(a \\\"b)\\\" #\\\\)) sym 42\")))
+       (write (list (read p) (read p) (read p) (read p))))",
+    "((a \"b)\" #\\)) sym 42 ())",
+  );
+  assert_eval(
+    "(let ((p (open-input-string \"#(1 #(2)) (#(3)) x\")))
+       (list (read p) (read p) (read p) (read p)))",
+    "([1 [2]] ([3]) x ())",
+  );
+}
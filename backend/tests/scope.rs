@@ -0,0 +1,82 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn closures_capture_their_defining_scope() {
+  assert_eval("(let ((x 5)) (let ((g (lambda () x))) (let ((x 6)) (g))))", "5");
+  assert_eval("(def x 1) (def f (lambda () x)) (let ((x 2)) (f))", "1");
+  assert_eval(
+    "(def make-adder (lambda (n) (lambda (x) (+ x n))))
+     (def add2 (make-adder 2))
+     (def add5 (make-adder 5))
+     (list (add2 1) (add5 1))",
+    "(3 6)",
+  );
+}
+
+#[test]
+fn closures_share_mutable_state() {
+  assert_eval(
+    "(def make-counter (lambda () (let ((n [0])) (lambda () (vector-set! n 0 (+ (vector-ref n 0) 1))))))
+     (def c (make-counter))
+     (c) (c)
+     (list (c) ((make-counter)))",
+    "(3 1)",
+  );
+}
+
+#[test]
+fn let_bindings_are_parallel() {
+  assert_eval("(def x 1) (let ((x 2) (y x)) y)", "1");
+  assert!(matches!(eval("(let ((a 1) (b a)) b)"), Err(RuntimeError::UndefinedSymbol { .. })));
+}
+
+#[test]
+fn let_does_not_leak() {
+  assert!(matches!(eval("(let ((y 1)) y) y"), Err(RuntimeError::UndefinedSymbol { .. })));
+}
+
+#[test]
+fn let_star_binds_sequentially() {
+  assert_eval("(let* ((a 1) (b (+ a 1)) (c (* b 2))) (list a b c))", "(1 2 4)");
+  assert_eval("(let* ((a 1) (a (+ a 1))) a)", "2");
+  assert_eval("(let* () 1)", "1");
+}
+
+#[test]
+fn multi_form_bodies() {
+  assert_eval("(let ((v [])) (vector-push! v 1) (vector-push! v 2) v)", "[1 2]");
+  assert_eval("((lambda () 1 2 3))", "3");
+}
+
+#[test]
+fn named_let() {
+  assert_eval(
+    "(let loop ((i 0) (acc ())) (if (= i 3) acc (loop (+ i 1) (cons i acc))))",
+    "(2 1 0)",
+  );
+  assert_eval(
+    "(let fact ((n 10)) (if (= n 0) 1 (* n (fact (- n 1)))))",
+    "3628800",
+  );
+  assert!(matches!(
+    eval("(let loop ((i 0)) i) loop"),
+    Err(RuntimeError::UndefinedSymbol { .. }),
+  ));
+}
+
+#[test]
+fn letrec_mutual_recursion() {
+  assert_eval(
+    "(letrec ((even? (lambda (n) (if (= n 0) true (odd? (- n 1)))))
+              (odd? (lambda (n) (if (= n 0) false (even? (- n 1))))))
+       (list (even? 10) (odd? 7) (even? 3)))",
+    "(true true false)",
+  );
+  assert_eval(
+    "(letrec* ((a 1) (f (lambda () (+ a b))) (b 2)) (f))",
+    "3",
+  );
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "serde")]
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use lispers_common::{backend::DefaultBackend, symbol::SymbolUsize};
+use lispers_backend::{Interpreter, Value};
+
+type Symbol = SymbolUsize;
+type Backend = DefaultBackend<Symbol>;
+
+fn eval(interpreter: &mut Interpreter<Symbol, Backend>, input: &str) -> Value<Symbol> {
+  let env = interpreter.default_env();
+  interpreter.eval_string(env, input).unwrap()
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Level {
+  Debug,
+  Limit(u8),
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+  name: String,
+  port: u16,
+  ratio: f64,
+  tags: Vec<String>,
+  level: Level,
+  fallback: Option<Level>,
+  limits: HashMap<String, i32>,
+  enabled: bool,
+}
+
+#[test]
+fn deserialize_struct() {
+  let mut interpreter = Interpreter::new();
+  let value = eval(&mut interpreter, r#"
+    {:name "server"
+     :port 8080
+     :ratio 1
+     :tags (list "a" :b)
+     :level :Debug
+     :fallback {:Limit 3}
+     :limits (persistent-hash-map :x -1)
+     :enabled true}
+  "#);
+
+  let config: Config = interpreter.from_value(&value).unwrap();
+
+  assert_eq!(config, Config {
+    name: "server".to_string(),
+    port: 8080,
+    ratio: 1.0,
+    tags: vec!["a".to_string(), "b".to_string()],
+    level: Level::Debug,
+    fallback: Some(Level::Limit(3)),
+    limits: HashMap::from([("x".to_string(), -1)]),
+    enabled: true,
+  });
+}
+
+#[test]
+fn deserialize_errors() {
+  let mut interpreter = Interpreter::new();
+
+  let value = eval(&mut interpreter, "{:port -1}");
+  let err = interpreter.from_value::<HashMap<String, u16>>(&value).unwrap_err();
+  assert!(err.to_string().contains("-1"), "{}", err);
+
+  let value = eval(&mut interpreter, "car");
+  assert!(interpreter.from_value::<String>(&value).is_err());
+
+  let value = eval(&mut interpreter, "{:name 1}");
+  assert!(interpreter.from_value::<Config>(&value).is_err());
+}
+
+#[test]
+fn serialize() {
+  let mut interpreter = Interpreter::new();
+  let value = eval(&mut interpreter, r#"(list 1 2.5 "x" #\c :kw () [true])"#);
+
+  let json = serde_json::to_string(&interpreter.serializable(&value)).unwrap();
+  assert_eq!(json, r#"[1,2.5,"x","c","kw",null,[true]]"#);
+
+  let value = eval(&mut interpreter, "{:a 1}");
+  let json = serde_json::to_string(&interpreter.serializable(&value)).unwrap();
+  assert_eq!(json, r#"{"a":1}"#);
+
+  assert!(serde_json::to_string(&value).is_err());
+  assert!(serde_json::to_string(&eval(&mut interpreter, "car")).is_err());
+}
+
+#[test]
+fn deserialize_value() {
+  let interpreter: Interpreter<Symbol, Backend> = Interpreter::new();
+  let value: Value<Symbol> = serde_json::from_str(r#"{"a": [1, 2.5, null, "é"], "b": true}"#).unwrap();
+
+  let json = serde_json::to_string(&interpreter.serializable(&value)).unwrap();
+  let round_trip: serde_json::Value = serde_json::from_str(&json).unwrap();
+  assert_eq!(round_trip, serde_json::json!({"a": [1, 2.5, null, "é"], "b": true}));
+}
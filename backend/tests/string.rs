@@ -0,0 +1,131 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn char_literals() {
+  assert_eval("(char->integer #\\a)", "97");
+  assert_eval("(char->integer #\\é)", "233");
+  assert_eval("(char->integer #\\()", "40");
+  assert_eval("(char? #\\a)", "true");
+  assert_eval("(char? \"a\")", "false");
+}
+
+#[test]
+fn char_names() {
+  assert_eval("(char->integer #\\space)", "32");
+  assert_eval("(char->integer #\\newline)", "10");
+  assert_eval("(char->integer #\\tab)", "9");
+  assert_eval("(char->integer #\\return)", "13");
+  assert_eval("(char->integer #\\nul)", "0");
+  assert!(matches!(eval("#\\bogus"), Err(RuntimeError::SyntaxError(..))));
+}
+
+#[test]
+fn char_hex_escapes() {
+  assert_eval("(char->integer #\\x41)", "65");
+  assert_eval("(char->integer #\\x1F600)", "128512");
+  assert_eval("(char->integer #\\x)", "120");
+  assert!(matches!(eval("#\\x+41"), Err(RuntimeError::SyntaxError(..))));
+  assert!(matches!(eval("#\\xZZ"), Err(RuntimeError::SyntaxError(..))));
+  assert!(matches!(eval("#\\xD800"), Err(RuntimeError::SyntaxError(..))));
+}
+
+#[test]
+fn char_conversions() {
+  assert_eval("(= (integer->char 955) #\\x3bb)", "true");
+  assert!(matches!(eval("(integer->char -1)"), Err(RuntimeError::TypeError { .. })));
+  assert!(matches!(eval("(integer->char 55296)"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn string_length() {
+  assert_eval("(string-length \"\")", "0");
+  assert_eval("(string-length \"héllo\")", "5");
+}
+
+#[test]
+fn substring() {
+  assert_eval("(substring \"héllo\" 1 3)", "él");
+  assert_eval("(substring \"héllo\" 2)", "llo");
+  assert_eval("(substring \"abc\" 3)", "");
+  assert!(matches!(
+    eval("(substring \"abc\" 4)"),
+    Err(RuntimeError::IndexOutOfBounds { index: 4, length: 3 }),
+  ));
+  assert!(matches!(
+    eval("(substring \"abc\" 2 1)"),
+    Err(RuntimeError::IndexOutOfBounds { .. }),
+  ));
+}
+
+#[test]
+fn string_split() {
+  assert_eval("(string-split \"  a b\\tc \")", "(a b c)");
+  assert_eval("(string-split \"a,b,,c\" \",\")", "(a b  c)");
+  assert_eval("(string-split \"a-b\" #\\-)", "(a b)");
+  assert_eval("(string-split \"héllo\" \"\")", "(h é l l o)");
+  assert_eval("(string-split \"\" \"\")", "()");
+  assert_eval("(length (string-split \"\" \",\"))", "1");
+}
+
+#[test]
+fn string_join() {
+  assert_eval("(string-join (list \"a\" \"b\") \", \")", "a, b");
+  assert_eval("(string-join () \",\")", "");
+}
+
+#[test]
+fn string_index() {
+  assert_eval("(string-index \"héllo\" \"l\")", "2");
+  assert_eval("(string-index \"hello\" #\\z)", "false");
+}
+
+#[test]
+fn string_case_and_trim() {
+  assert_eval("(string-upcase \"straße\")", "STRASSE");
+  assert_eval("(string-downcase \"ÉTÉ\")", "été");
+  assert_eval("(string-trim \"  a b \\n\")", "a b");
+}
+
+#[test]
+fn string_to_number() {
+  assert_eval("(string->number \"42\")", "42");
+  assert_eval("(string->number \" -7 \")", "-7");
+  assert_eval("(string->number \"1.5\")", "1.5");
+  assert_eval("(string->number \"1e3\")", "1000");
+  assert_eval("(string->number \"ff\" 16)", "255");
+  assert_eval("(string->number \"abc\")", "false");
+  assert_eval("(string->number \"\")", "false");
+  assert_eval("(string->number \"nan\")", "false");
+  assert_eval("(string->number \"inf\")", "false");
+  assert_eval("(string->number \"-infinity\")", "false");
+  assert_eval("(string->number \"1e400\")", "false");
+  assert_eval("(string->number \"-1e400\")", "false");
+  assert_eval("(string->number \"1e-400\")", "0");
+  assert!(matches!(eval("(string->number \"1\" 1)"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn number_to_string() {
+  assert_eval("(number->string 255 16)", "ff");
+  assert_eval("(number->string -5 2)", "-101");
+  assert_eval("(number->string 0 8)", "0");
+  assert_eval("(number->string 1.5)", "1.5");
+}
+
+#[test]
+fn string_list_conversions() {
+  assert_eval("(length (string->list \"héllo\"))", "5");
+  assert_eval("(list->string (string->list \"héllo\"))", "héllo");
+  assert_eval("(list->string ())", "");
+  assert!(matches!(eval("(list->string (list 1))"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn string_contains_replace() {
+  assert_eval("(string-contains? \"hello\" \"ell\")", "true");
+  assert_eval("(string-contains? \"hello\" \"\")", "true");
+  assert_eval("(string-replace \"a-b-c\" \"-\" \"+\")", "a+b+c");
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn type_of() {
+  assert_eval("(type-of true)", ":boolean");
+  assert_eval("(type-of 1)", ":integer");
+  assert_eval("(type-of 1.5)", ":float");
+  assert_eval("(type-of #\\a)", ":char");
+  assert_eval("(type-of \"a\")", ":string");
+  assert_eval("(type-of (quote a))", ":symbol");
+  assert_eval("(type-of :a)", ":keyword");
+  assert_eval("(type-of ())", ":list");
+  assert_eval("(type-of [])", ":vector");
+  assert_eval("(type-of {})", ":map");
+  assert_eval("(type-of (persistent-vector))", ":persistent-vector");
+  assert_eval("(type-of (persistent-hash-map))", ":persistent-map");
+  assert_eval("(type-of car)", ":function");
+  assert_eval("(type-of (lambda () 1))", ":function");
+  assert_eval("(type-of (open-input-string \"\"))", ":port");
+}
+
+#[test]
+fn scalar_predicates() {
+  assert_eval("(boolean? false)", "true");
+  assert_eval("(boolean? ())", "false");
+  assert_eval("(integer? 1)", "true");
+  assert_eval("(integer? 1.0)", "false");
+  assert_eval("(float? 1.0)", "true");
+  assert_eval("(float? 1)", "false");
+  assert_eval("(number? 1)", "true");
+  assert_eval("(number? 1.5)", "true");
+  assert_eval("(number? \"1\")", "false");
+  assert_eval("(string? \"a\")", "true");
+  assert_eval("(string? #\\a)", "false");
+  assert_eval("(symbol? (quote a))", "true");
+  assert_eval("(symbol? :a)", "false");
+}
+
+#[test]
+fn collection_predicates() {
+  assert_eval("(list? ())", "true");
+  assert_eval("(list? (list 1))", "true");
+  assert_eval("(list? [])", "false");
+  assert_eval("(pair? (list 1))", "true");
+  assert_eval("(pair? ())", "false");
+  assert_eval("(vector? [1])", "true");
+  assert_eval("(vector? (persistent-vector 1))", "true");
+  assert_eval("(vector? (list 1))", "false");
+  assert_eval("(map? {})", "true");
+  assert_eval("(map? (persistent-hash-map))", "true");
+  assert_eval("(map? [])", "false");
+}
+
+#[test]
+fn procedure_predicates() {
+  assert_eval("(procedure? car)", "true");
+  assert_eval("(procedure? (lambda (x) x))", "true");
+  assert_eval("(procedure? :a)", "false");
+  assert_eval("(port? (open-output-string))", "true");
+  assert_eval("(port? \"\")", "false");
+}
+
+#[test]
+fn predicate_arity() {
+  assert!(matches!(
+    eval("(integer?)"),
+    Err(RuntimeError::TooFewArguments { min: 1, max: Some(1), got: 0 }),
+  ));
+  assert!(matches!(
+    eval("(list? 1 2)"),
+    Err(RuntimeError::TooManyArguments { min: 1, max: Some(1), got: 2 }),
+  ));
+}
+
+#[test]
+fn arity() {
+  assert_eval("(arity (lambda () 1))", "(0 0)");
+  assert_eval("(arity (lambda (a b) a))", "(2 2)");
+  assert_eval("(arity (lambda (a &optional b c) a))", "(1 3)");
+  assert_eval("(arity (lambda (a &rest more) a))", "(1 false)");
+  assert_eval("(arity car)", "false");
+  assert!(matches!(eval("(arity 1)"), Err(RuntimeError::TypeError { .. })));
+}
+
+#[test]
+fn procedure_name() {
+  assert_eval("(procedure-name car)", "car");
+  assert_eval("(def f (lambda (x) x)) (procedure-name f)", "f");
+  assert_eval("(procedure-name (lambda (x) x))", "false");
+  assert!(matches!(eval("(procedure-name :a)"), Err(RuntimeError::TypeError { .. })));
+}
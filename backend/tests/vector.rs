@@ -0,0 +1,73 @@
+mod common;
+
+use lispers_backend::RuntimeError;
+use common::{eval, assert_eval};
+
+#[test]
+fn literal() {
+  assert_eval("[]", "[]");
+  assert_eval("[1 (+ 1 1) 3]", "[1 2 3]");
+  assert_eval("[[1] (list 2)]", "[[1] (2)]");
+}
+
+#[test]
+fn vector() {
+  assert_eval("(vector)", "[]");
+  assert_eval("(vector 1 :a \"b\")", "[1 :a b]");
+}
+
+#[test]
+fn vector_ref() {
+  assert_eval("(vector-ref [1 2 3] 0)", "1");
+  assert_eval("(vector-ref [1 2 3] 2)", "3");
+  assert!(matches!(
+    eval("(vector-ref [1 2 3] 3)"),
+    Err(RuntimeError::IndexOutOfBounds { index: 3, length: 3 }),
+  ));
+  assert!(matches!(
+    eval("(vector-ref [1 2 3] -1)"),
+    Err(RuntimeError::IndexOutOfBounds { index: -1, length: 3 }),
+  ));
+  assert!(matches!(
+    eval("(vector-ref (list 1) 0)"),
+    Err(RuntimeError::TypeError { .. }),
+  ));
+}
+
+#[test]
+fn vector_set() {
+  assert_eval("(def v [1 2 3]) (vector-set! v 1 :b) v", "[1 :b 3]");
+  assert_eval("(vector-set! [1 2 3] 0 :a)", ":a");
+  assert!(matches!(
+    eval("(vector-set! [] 0 1)"),
+    Err(RuntimeError::IndexOutOfBounds { index: 0, length: 0 }),
+  ));
+}
+
+#[test]
+fn vector_length() {
+  assert_eval("(vector-length [])", "0");
+  assert_eval("(vector-length [1 2 3])", "3");
+  assert!(matches!(
+    eval("(vector-length [1] [2])"),
+    Err(RuntimeError::TooManyArguments { min: 1, max: Some(1), got: 2 }),
+  ));
+}
+
+#[test]
+fn vector_push() {
+  assert_eval("(def v []) (vector-push! v 1) (vector-push! v 2) v", "[1 2]");
+}
+
+#[test]
+fn shared_mutation() {
+  assert_eval("(def v [1]) (def w v) (vector-push! w 2) v", "[1 2]");
+}
+
+#[test]
+fn conversions() {
+  assert_eval("(vector->list [1 2 3])", "(1 2 3)");
+  assert_eval("(vector->list [])", "()");
+  assert_eval("(list->vector (list 1 2 3))", "[1 2 3]");
+  assert_eval("(list->vector ())", "[]");
+}
@@ -5,12 +5,16 @@ pub enum Literal<S: Symbol> {
   Boolean(bool),
   Integer(i64),
   Float(f64),
+  Char(char),
   String(String),
   Symbol(S),
+  Keyword(S),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum SExpression<S: Symbol> {
   Literal(Literal<S>),
   List(Vec<SExpression<S>>),
+  Vector(Vec<SExpression<S>>),
+  Map(Vec<(SExpression<S>, SExpression<S>)>),
 }
@@ -15,6 +15,8 @@ peg::parser!{
     ) -> SExpression<S>
       = literal(interner)
       / list(interner)
+      / vector(interner)
+      / map(interner)
 
     rule list<S: Symbol, B: Backend<S>>(
       interner: &mut StringInterner<B>
@@ -22,14 +24,36 @@ peg::parser!{
       = [Token::ParenOpen] children:s_expression(interner)* [Token::ParenClose]
       { SExpression::List(children) }
 
+    rule vector<S: Symbol, B: Backend<S>>(
+      interner: &mut StringInterner<B>
+    ) -> SExpression<S>
+      = [Token::BracketOpen] children:s_expression(interner)* [Token::BracketClose]
+      { SExpression::Vector(children) }
+      / [Token::HashParenOpen] children:s_expression(interner)* [Token::ParenClose]
+      { SExpression::Vector(children) }
+
+    rule map<S: Symbol, B: Backend<S>>(
+      interner: &mut StringInterner<B>
+    ) -> SExpression<S>
+      = [Token::BraceOpen] entries:map_entry(interner)* [Token::BraceClose]
+      { SExpression::Map(entries) }
+
+    rule map_entry<S: Symbol, B: Backend<S>>(
+      interner: &mut StringInterner<B>
+    ) -> (SExpression<S>, SExpression<S>)
+      = key:s_expression(interner) val:s_expression(interner)
+      { (key, val) }
+
     rule literal<S: Symbol, B: Backend<S>>(
       interner: &mut StringInterner<B>
     ) -> SExpression<S>
       = literal_boolean()
       / literal_integer()
       / literal_float()
+      / literal_char()
       / literal_string()
       / literal_symbol(interner)
+      / literal_keyword(interner)
 
     rule literal_boolean<S: Symbol>() -> SExpression<S>
       = literal_boolean_true()
@@ -69,6 +93,10 @@ peg::parser!{
       = [Token::Float(n)]
       { SExpression::Literal(Literal::Float(*n)) }
 
+    rule literal_char<S: Symbol>() -> SExpression<S>
+      = [Token::Char(c)]
+      { SExpression::Literal(Literal::Char(*c)) }
+
     rule literal_string<S: Symbol>() -> SExpression<S>
       = [Token::String(s)]
       { SExpression::Literal(Literal::String(s.clone())) }
@@ -81,5 +109,14 @@ peg::parser!{
         let sym = interner.get_or_intern(s);
         SExpression::Literal(Literal::Symbol(sym))
       }
+
+    rule literal_keyword<S: Symbol, B: Backend<S>>(
+      interner: &mut StringInterner<B>
+    ) -> SExpression<S>
+      = [Token::Keyword(s)]
+      {
+        let sym = interner.get_or_intern(s);
+        SExpression::Literal(Literal::Keyword(sym))
+      }
   }
 }
@@ -56,20 +56,78 @@ impl<'source> TokenStream<'source> {
 
     Ok(token_stream)
   }
+
+  /// Index of the token after the datum starting at `pos`.
+  fn skip_datum(&self, pos: usize) -> usize {
+    let mut depth = 0usize;
+    let mut pos = pos;
+
+    while let Some((token, _)) = self.tokens.get(pos) {
+      pos += 1;
+
+      match token {
+        Token::ParenOpen | Token::BracketOpen | Token::HashParenOpen | Token::BraceOpen => {
+          depth += 1;
+        },
+        Token::ParenClose | Token::BracketClose | Token::BraceClose => {
+          depth = depth.saturating_sub(1);
+        },
+        _ => {},
+      }
+
+      if depth == 0 {
+        break;
+      }
+    }
+
+    pos
+  }
+
+  /// Line and column of the datum reached by following `path`, a list of
+  /// child indices starting with the index of a top-level expression. Map
+  /// entries count as two children, the key then the value.
+  pub fn locate(&self, path: &[usize]) -> Option<(usize, usize)> {
+    let mut pos = 0;
+
+    for (depth, index) in path.iter().enumerate() {
+      if depth > 0 {
+        match self.tokens.get(pos) {
+          Some((Token::ParenOpen | Token::BracketOpen | Token::HashParenOpen | Token::BraceOpen, _)) => {
+            pos += 1;
+          },
+          _ => return None,
+        }
+      }
+
+      for _ in 0..*index {
+        match self.tokens.get(pos) {
+          None | Some((Token::ParenClose | Token::BracketClose | Token::BraceClose, _)) => {
+            return None;
+          },
+          Some(_) => pos = self.skip_datum(pos),
+        }
+      }
+    }
+
+    match self.tokens.get(pos) {
+      None | Some((Token::ParenClose | Token::BracketClose | Token::BraceClose, _)) => None,
+      Some((_, span)) => Some(self.linecol_lookup.get(span.start)),
+    }
+  }
 }
 
 impl<'source> peg::Parse for TokenStream<'source> {
   type PositionRepr = TokenLocation;
 
-  fn start<'input>(&'input self) -> usize {
+  fn start(&self) -> usize {
     0
   }
 
-  fn is_eof<'input>(&'input self, pos: usize) -> bool {
+  fn is_eof(&self, pos: usize) -> bool {
     pos >= self.tokens.len()
   }
 
-  fn position_repr<'input>(&'input self, pos: usize) -> Self::PositionRepr {
+  fn position_repr(&self, pos: usize) -> Self::PositionRepr {
     let (token, linecol) = match self.tokens.get(pos) {
       Some((token, span)) => {
         (Some(token.clone()), self.linecol_lookup.get(span.start))
@@ -120,7 +178,7 @@ impl<'source, 'input> peg::ParseSlice<'input> for TokenStream<'source> {
 
   fn parse_slice(&'input self, begin_pos: usize, end_pos: usize) -> Self::Slice {
     self.tokens[begin_pos .. end_pos]
-      .into_iter()
+      .iter()
       .map(|(token, _)| token)
       .collect()
   }
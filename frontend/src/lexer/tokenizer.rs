@@ -9,15 +9,38 @@ pub enum Token {
   #[token(")")]
   ParenClose,
 
+  #[token("[")]
+  BracketOpen,
+
+  #[token("]")]
+  BracketClose,
+
+  #[token("#(")]
+  HashParenOpen,
+
+  #[token("{")]
+  BraceOpen,
+
+  #[token("}")]
+  BraceClose,
+
   #[token("true")]
   True,
 
   #[token("false")]
   False,
 
-  #[regex("[^ \\t\\r\\n\\f\"\\(\\)]+", |lex| lex.slice().parse())]
+  #[regex("[^ \\t\\r\\n\\f\";\\(\\)\\[\\]\\{\\}]+", |lex| lex.slice().parse())]
   Symbol(String),
 
+  #[regex(":[^ \\t\\r\\n\\f\";\\(\\)\\[\\]\\{\\}]+", |lex| lex.slice()[1..].parse())]
+  Keyword(String),
+
+  #[regex("#\\\\[^ \\t\\r\\n\\f][^ \\t\\r\\n\\f\"\\(\\)\\[\\]\\{\\}]*", |lex| {
+    parse_char(&lex.slice()[2..])
+  })]
+  Char(char),
+
   #[regex("\"(?:[^\"]|\\\\\")*\"", |lex| {
     unescape(lex.slice())
   })]
@@ -26,6 +49,10 @@ pub enum Token {
   #[regex(r"[+-]?((\d+\.?\d*)|(\.\d+))(([eE][+-]?)?\d+)?", |lex| {
     lex.slice().parse()
   }, priority = 2)]
+  #[token("+inf.0", |_| f64::INFINITY)]
+  #[token("-inf.0", |_| f64::NEG_INFINITY)]
+  #[token("+nan.0", |_| f64::NAN)]
+  #[token("-nan.0", |_| -f64::NAN)]
   Float(f64),
 
   #[regex(r"0b_*[01][_01]*", |lex| {
@@ -50,9 +77,35 @@ pub enum Token {
 
   #[error]
   #[regex(r"[ \t\r\n\f]+", logos::skip)]
+  #[regex(r";[^\n]*", logos::skip)]
   Error,
 }
 
+fn parse_char(name: &str) -> Option<char> {
+  let mut chars = name.chars();
+
+  match (chars.next(), chars.next()) {
+    (Some(c), None) => Some(c),
+    _ => match name {
+      "space" => Some(' '),
+      "newline" => Some('\n'),
+      "tab" => Some('\t'),
+      "return" => Some('\r'),
+      "nul" => Some('\0'),
+      _ => {
+        let code = name.strip_prefix('x')?;
+
+        if code.is_empty() || !code.chars().all(|c| c.is_ascii_hexdigit()) {
+          return None;
+        }
+
+        let code = u32::from_str_radix(code, 16).ok()?;
+        char::from_u32(code)
+      },
+    },
+  }
+}
+
 impl std::fmt::Display for Token {
   fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
     write!(f, "{:?}", self)
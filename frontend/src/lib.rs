@@ -24,3 +24,10 @@ pub fn parse<S: Symbol, B: Backend<S>>(
   let ast = grammar::module_parser::module(&stream, interner)?;
   Ok(ast)
 }
+
+/// Line and column of a datum in `input`, see `TokenStream::locate`.
+pub fn locate(input: &str, path: &[usize]) -> Option<(usize, usize)> {
+  lexer::TokenStream::new(None, input)
+    .ok()
+    .and_then(|stream| stream.locate(path))
+}
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use clap::{arg, command, value_parser};
+use clap::{arg, command, value_parser, ArgAction};
 use rustyline::error::ReadlineError;
 use rustyline::DefaultEditor;
 
@@ -19,11 +19,25 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
       .required(false)
       .value_parser(value_parser!(PathBuf))
     )
+    .arg(
+      arg!(
+        -L --lib <DIR> "Add a directory to the module search path"
+      )
+      .required(false)
+      .action(ArgAction::Append)
+      .value_parser(value_parser!(PathBuf))
+    )
     .get_matches();
 
   let mut interpreter: Interpreter<Symbol, Backend> = Interpreter::new();
   let env = interpreter.default_env();
 
+  if let Some(paths) = matches.get_many::<PathBuf>("lib") {
+    for path in paths {
+      interpreter.add_search_path(path);
+    }
+  }
+
   if let Some(input_path) = matches.get_one::<PathBuf>("input") {
     if let Err(err) = interpreter.eval_file(env.clone(), input_path) {
       eprintln!("{}", err);